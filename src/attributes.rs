@@ -0,0 +1,330 @@
+//! Access to ODBC connection and statement attributes not exposed by `odbc` crate.
+
+use odbc::{ffi, Connection as OdbcConnection, Handle as OdbcHandle, Statement};
+
+use crate::result_set::DriverWarning;
+use crate::{Diagnostic, OdbcError};
+
+/// Attribute access outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Accepted {
+    /// Driver accepted the value as provided.
+    Exact,
+    /// Driver returned `SQL_SUCCESS_WITH_INFO` which usually means it substituted similar value (e.g. clamped it).
+    WithInfo,
+}
+
+/// Check outcome of raw FFI call made on given handle; on failure first diagnostic record of the handle is provided as
+/// cause of the error.
+pub(crate) fn check(
+    ret: ffi::SQLRETURN,
+    handle_type: ffi::HandleType,
+    handle: ffi::SQLHANDLE,
+    context: &'static str,
+) -> Result<Accepted, OdbcError> {
+    match ret {
+        ffi::SQL_SUCCESS => Ok(Accepted::Exact),
+        ffi::SQL_SUCCESS_WITH_INFO => Ok(Accepted::WithInfo),
+        _ => Err(OdbcError(get_diagnostic_record(handle_type, handle, 1).map(Diagnostic::Handle), context)),
+    }
+}
+
+// `SQLGetDiagField` header field holding return code of the last function called on the handle
+const SQL_DIAG_RETURNCODE: ffi::SQLSMALLINT = 1;
+
+// not provided by `odbc` crate; implemented by the driver manager
+#[cfg_attr(windows, link(name = "odbc32"))]
+#[cfg_attr(not(windows), link(name = "odbc"))]
+extern "system" {
+    fn SQLGetDiagField(
+        handle_type: ffi::HandleType,
+        handle: ffi::SQLHANDLE,
+        record: ffi::SQLSMALLINT,
+        diag_identifier: ffi::SQLSMALLINT,
+        diag_info: ffi::SQLPOINTER,
+        buffer_length: ffi::SQLSMALLINT,
+        string_length: *mut ffi::SQLSMALLINT,
+    ) -> ffi::SQLRETURN;
+}
+
+/// Return code of the last function called on given handle (e.g. `SQLFetch` called by `odbc` crate that does not
+/// expose it).
+///
+/// Unlike `SQLGetDiagRec` this is answered by the driver manager from the diagnostic header so it is cheap to call.
+pub(crate) fn last_return_code(handle_type: ffi::HandleType, handle: ffi::SQLHANDLE) -> Option<ffi::SQLRETURN> {
+    let mut ret: ffi::SQLRETURN = ffi::SQL_SUCCESS;
+    match unsafe {
+        SQLGetDiagField(
+            handle_type,
+            handle,
+            0,
+            SQL_DIAG_RETURNCODE,
+            &mut ret as *mut ffi::SQLRETURN as ffi::SQLPOINTER,
+            0,
+            std::ptr::null_mut(),
+        )
+    } {
+        ffi::SQL_SUCCESS | ffi::SQL_SUCCESS_WITH_INFO => Some(ret),
+        _ => None,
+    }
+}
+
+/// Read diagnostic record (1 based) of given handle (`SQLGetDiagRec`).
+///
+/// Returns `None` if there is no such record.
+pub(crate) fn get_diagnostic_record(
+    handle_type: ffi::HandleType,
+    handle: ffi::SQLHANDLE,
+    record: ffi::SQLSMALLINT,
+) -> Option<DriverWarning> {
+    if handle.is_null() {
+        return None;
+    }
+
+    let mut state = [0u8; 6];
+    let mut native_error: ffi::SQLINTEGER = 0;
+    let mut message = [0u8; 512];
+    let mut length: ffi::SQLSMALLINT = 0;
+
+    match unsafe {
+        ffi::SQLGetDiagRec(
+            handle_type,
+            handle,
+            record,
+            state.as_mut_ptr(),
+            &mut native_error,
+            message.as_mut_ptr(),
+            message.len() as ffi::SQLSMALLINT,
+            &mut length,
+        )
+    } {
+        ffi::SQL_SUCCESS | ffi::SQL_SUCCESS_WITH_INFO => {
+            let length = (length.max(0) as usize).min(message.len() - 1);
+            Some(DriverWarning {
+                state: String::from_utf8_lossy(&state[..5]).into_owned(),
+                native_error,
+                message: String::from_utf8_lossy(&message[..length]).into_owned(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Set integer valued environment attribute.
+///
+/// Null environment handle sets process level attributes (e.g. `SQL_ATTR_CONNECTION_POOLING`).
+pub(crate) fn set_environment_attribute_integer(
+    environment: ffi::SQLHENV,
+    attribute: ffi::EnvironmentAttribute,
+    value: ffi::SQLUINTEGER,
+    context: &'static str,
+) -> Result<Accepted, OdbcError> {
+    check(unsafe {
+        ffi::SQLSetEnvAttr(environment, attribute, value as usize as ffi::SQLPOINTER, 0)
+    }, ffi::SQL_HANDLE_ENV, environment as ffi::SQLHANDLE, context)
+}
+
+/// Set integer valued connection attribute.
+///
+/// Can be used with not yet connected data source handle for attributes that need to be set before connecting.
+pub(crate) fn set_connection_attribute_integer<H: OdbcHandle<To = ffi::Dbc>>(
+    connection: &H,
+    attribute: ffi::SqlConnectionAttribute,
+    value: ffi::SQLUINTEGER,
+    context: &'static str,
+) -> Result<Accepted, OdbcError> {
+    check(unsafe {
+        ffi::SQLSetConnectAttr(connection.handle(), attribute, value as usize as ffi::SQLPOINTER, 0)
+    }, ffi::SQL_HANDLE_DBC, unsafe { connection.handle() } as ffi::SQLHANDLE, context)
+}
+
+/// Get integer valued connection attribute.
+pub(crate) fn get_connection_attribute_integer(
+    connection: &OdbcConnection<'static>,
+    attribute: ffi::SqlConnectionAttribute,
+    context: &'static str,
+) -> Result<ffi::SQLUINTEGER, OdbcError> {
+    let mut value: ffi::SQLUINTEGER = 0;
+    check(unsafe {
+        ffi::SQLGetConnectAttr(
+            connection.handle(),
+            attribute,
+            &mut value as *mut ffi::SQLUINTEGER as ffi::SQLPOINTER,
+            0,
+            std::ptr::null_mut(),
+        )
+    }, ffi::SQL_HANDLE_DBC, unsafe { connection.handle() } as ffi::SQLHANDLE, context)?;
+    Ok(value)
+}
+
+/// Set string valued connection attribute.
+pub(crate) fn set_connection_attribute_string(
+    connection: &OdbcConnection<'static>,
+    attribute: ffi::SqlConnectionAttribute,
+    value: &str,
+    context: &'static str,
+) -> Result<Accepted, OdbcError> {
+    check(unsafe {
+        ffi::SQLSetConnectAttr(connection.handle(), attribute, value.as_ptr() as ffi::SQLPOINTER, value.len() as ffi::SQLINTEGER)
+    }, ffi::SQL_HANDLE_DBC, unsafe { connection.handle() } as ffi::SQLHANDLE, context)
+}
+
+/// Get string valued connection attribute.
+pub(crate) fn get_connection_attribute_string(
+    connection: &OdbcConnection<'static>,
+    attribute: ffi::SqlConnectionAttribute,
+    context: &'static str,
+) -> Result<String, OdbcError> {
+    let mut buffer = vec![0u8; 256];
+    loop {
+        let mut length: ffi::SQLINTEGER = 0;
+        check(unsafe {
+            ffi::SQLGetConnectAttr(
+                connection.handle(),
+                attribute,
+                buffer.as_mut_ptr() as ffi::SQLPOINTER,
+                buffer.len() as ffi::SQLINTEGER,
+                &mut length,
+            )
+        }, ffi::SQL_HANDLE_DBC, unsafe { connection.handle() } as ffi::SQLHANDLE, context)?;
+
+        let length = length.max(0) as usize;
+        // value was truncated; retry with buffer big enough for the value and null terminator
+        if length >= buffer.len() {
+            buffer.resize(length + 1, 0);
+            continue;
+        }
+
+        buffer.truncate(length);
+        return Ok(String::from_utf8_lossy(&buffer).into_owned());
+    }
+}
+
+/// Get string valued information about the driver and data source (`SQLGetInfo`).
+pub(crate) fn get_info_string(
+    connection: &OdbcConnection<'static>,
+    info_type: ffi::InfoType,
+    context: &'static str,
+) -> Result<String, OdbcError> {
+    let mut buffer = vec![0u8; 256];
+    loop {
+        let mut length: ffi::SQLSMALLINT = 0;
+        check(unsafe {
+            ffi::SQLGetInfo(
+                connection.handle(),
+                info_type,
+                buffer.as_mut_ptr() as ffi::SQLPOINTER,
+                buffer.len() as ffi::SQLSMALLINT,
+                &mut length,
+            )
+        }, ffi::SQL_HANDLE_DBC, unsafe { connection.handle() } as ffi::SQLHANDLE, context)?;
+
+        let length = length.max(0) as usize;
+        // value was truncated; retry with buffer big enough for the value and null terminator
+        if length >= buffer.len() {
+            buffer.resize(length + 1, 0);
+            continue;
+        }
+
+        buffer.truncate(length);
+        return Ok(String::from_utf8_lossy(&buffer).into_owned());
+    }
+}
+
+/// Commit or roll back transaction of the connection (`SQLEndTran`).
+pub(crate) fn end_transaction(
+    connection: &OdbcConnection<'static>,
+    completion: ffi::SqlCompletionType,
+    context: &'static str,
+) -> Result<Accepted, OdbcError> {
+    check(unsafe {
+        ffi::SQLEndTran(ffi::SQL_HANDLE_DBC, connection.handle() as ffi::SQLHANDLE, completion)
+    }, ffi::SQL_HANDLE_DBC, unsafe { connection.handle() } as ffi::SQLHANDLE, context)
+}
+
+/// Set integer valued statement attribute.
+pub(crate) fn set_statement_attribute_integer<S, R>(
+    statement: &Statement<'_, '_, S, R>,
+    attribute: ffi::SqlStatementAttribute,
+    value: ffi::SQLULEN,
+    context: &'static str,
+) -> Result<Accepted, OdbcError> {
+    check(unsafe {
+        ffi::SQLSetStmtAttr(statement.handle(), attribute, value as ffi::SQLPOINTER, 0)
+    }, ffi::SQL_HANDLE_STMT, unsafe { statement.handle() } as ffi::SQLHANDLE, context)
+}
+
+/// Get integer valued statement attribute.
+pub(crate) fn get_statement_attribute_integer<S, R>(
+    statement: &Statement<'_, '_, S, R>,
+    attribute: ffi::SqlStatementAttribute,
+    context: &'static str,
+) -> Result<ffi::SQLULEN, OdbcError> {
+    let mut value: ffi::SQLULEN = 0;
+    check(unsafe {
+        ffi::SQLGetStmtAttr(
+            statement.handle(),
+            attribute,
+            &mut value as *mut ffi::SQLULEN as ffi::SQLPOINTER,
+            0,
+            std::ptr::null_mut(),
+        )
+    }, ffi::SQL_HANDLE_STMT, unsafe { statement.handle() } as ffi::SQLHANDLE, context)?;
+    Ok(value)
+}
+
+/// Get string valued column attribute (`SQLColAttribute`) of result set column (1 based).
+pub(crate) fn get_column_attribute_string<S, R>(
+    statement: &Statement<'_, '_, S, R>,
+    column: u16,
+    attribute: ffi::Desc,
+    context: &'static str,
+) -> Result<String, OdbcError> {
+    let mut buffer = vec![0u8; 256];
+    loop {
+        let mut length: ffi::SQLSMALLINT = 0;
+        check(unsafe {
+            ffi::SQLColAttribute(
+                statement.handle(),
+                column,
+                attribute,
+                buffer.as_mut_ptr() as ffi::SQLPOINTER,
+                buffer.len() as ffi::SQLSMALLINT,
+                &mut length,
+                std::ptr::null_mut(),
+            )
+        }, ffi::SQL_HANDLE_STMT, unsafe { statement.handle() } as ffi::SQLHANDLE, context)?;
+
+        let length = length.max(0) as usize;
+        // value was truncated; retry with buffer big enough for the value and null terminator
+        if length >= buffer.len() {
+            buffer.resize(length + 1, 0);
+            continue;
+        }
+
+        buffer.truncate(length);
+        return Ok(String::from_utf8_lossy(&buffer).into_owned());
+    }
+}
+
+/// Get numeric valued column attribute (`SQLColAttribute`) of result set column (1 based).
+pub(crate) fn get_column_attribute_integer<S, R>(
+    statement: &Statement<'_, '_, S, R>,
+    column: u16,
+    attribute: ffi::Desc,
+    context: &'static str,
+) -> Result<ffi::SQLLEN, OdbcError> {
+    let mut value: ffi::SQLLEN = 0;
+    check(unsafe {
+        ffi::SQLColAttribute(
+            statement.handle(),
+            column,
+            attribute,
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            &mut value,
+        )
+    }, ffi::SQL_HANDLE_STMT, unsafe { statement.handle() } as ffi::SQLHANDLE, context)?;
+    Ok(value)
+}
@@ -0,0 +1,306 @@
+use std::fmt;
+
+/// Builds ODBC connection string from key/value attribute pairs escaping values as needed.
+///
+/// Values containing special characters (`;`, `{`, `}`, `=`) or leading/trailing spaces are wrapped in braces with
+/// embedded closing braces doubled according to ODBC rules.
+///
+/// Example
+/// ========
+/// ```rust
+/// use odbc_iter::ConnectionStringBuilder;
+///
+/// let connection_string = ConnectionStringBuilder::new()
+///     .attribute("Driver", "ODBC Driver 17 for SQL Server")
+///     .attribute("Server", "localhost")
+///     .attribute("UID", "sa")
+///     .attribute("PWD", "pa;ss}word")
+///     .build();
+///
+/// assert_eq!(connection_string, "Driver=ODBC Driver 17 for SQL Server;Server=localhost;UID=sa;PWD={pa;ss}}word};");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ConnectionStringBuilder {
+    attributes: Vec<(String, String)>,
+}
+
+impl ConnectionStringBuilder {
+    pub fn new() -> ConnectionStringBuilder {
+        Default::default()
+    }
+
+    /// Add attribute to the connection string.
+    /// If attribute with the same name (case insensitive) was already added its value is replaced.
+    pub fn attribute(&mut self, key: &str, value: &str) -> &mut ConnectionStringBuilder {
+        if let Some(attribute) = self.attributes.iter_mut().find(|(k, _)| k.eq_ignore_ascii_case(key)) {
+            attribute.1 = value.to_owned();
+        } else {
+            self.attributes.push((key.to_owned(), value.to_owned()));
+        }
+        self
+    }
+
+    /// Build escaped connection string.
+    pub fn build(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn needs_escaping(value: &str) -> bool {
+    value.contains(|c| c == ';' || c == '{' || c == '}' || c == '=')
+        || value.starts_with(' ')
+        || value.ends_with(' ')
+}
+
+impl fmt::Display for ConnectionStringBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (key, value) in &self.attributes {
+            if needs_escaping(value) {
+                write!(f, "{}={{{}}};", key, value.replace('}', "}}"))?;
+            } else {
+                write!(f, "{}={};", key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse ODBC connection string into list of key/value attribute pairs unescaping braced values.
+pub(crate) fn parse_connection_string(connection_string: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut chars = connection_string.chars().peekable();
+
+    loop {
+        let key = chars.by_ref().take_while(|c| *c != '=').collect::<String>();
+        let key = key.trim().trim_start_matches(';').trim();
+        if key.is_empty() && chars.peek().is_none() {
+            return attributes;
+        }
+
+        let mut value = String::new();
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                if c == '}' {
+                    if chars.peek() == Some(&'}') {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                value.push(c);
+            }
+            // skip up to attribute separator
+            chars.by_ref().take_while(|c| *c != ';').for_each(drop);
+        } else {
+            value.extend(chars.by_ref().take_while(|c| *c != ';'));
+            let len = value.trim_end().len();
+            value.truncate(len);
+        }
+
+        if !key.is_empty() {
+            attributes.push((key.to_owned(), value));
+        }
+    }
+}
+
+fn is_secret(key: &str) -> bool {
+    key.eq_ignore_ascii_case("PWD") || key.eq_ignore_ascii_case("PASSWORD")
+}
+
+/// Rebuild connection string with password attribute values replaced by `***`.
+pub(crate) fn redact_connection_string(connection_string: &str) -> String {
+    let mut builder = ConnectionStringBuilder::new();
+    for (key, value) in parse_connection_string(connection_string) {
+        builder.attribute(&key, if is_secret(&key) { "***" } else { &value });
+    }
+    builder.build()
+}
+
+/// Check for obvious mistakes in connection string before passing it to the driver which would likely fail with less clear error.
+/// Returns description of the problem.
+pub(crate) fn validate_connection_string(connection_string: &str) -> Result<(), &'static str> {
+    if connection_string.trim().is_empty() {
+        return Err("validating connection string: connection string is empty")
+    }
+
+    if !connection_string.contains('=') {
+        return Err("validating connection string: expected KEY=value attributes separated with ';'")
+    }
+
+    let has_data_source = parse_connection_string(connection_string).iter().any(|(key, value)| {
+        (key.eq_ignore_ascii_case("DRIVER") || key.eq_ignore_ascii_case("DSN") || key.eq_ignore_ascii_case("FILEDSN"))
+            && !value.trim().is_empty()
+    });
+    if !has_data_source {
+        return Err("validating connection string: connection string missing DRIVER, DSN or FILEDSN attribute")
+    }
+
+    Ok(())
+}
+
+/// Summary of connection string attributes safe for logging; the password is never stored.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConnectionInfo {
+    pub driver: Option<String>,
+    pub dsn: Option<String>,
+    pub server: Option<String>,
+    pub database: Option<String>,
+    pub user: Option<String>,
+    /// `true` if connection string contained a password.
+    pub password: bool,
+}
+
+impl ConnectionInfo {
+    /// Extract connection information from ODBC connection string skipping any secrets.
+    pub fn from_connection_string(connection_string: &str) -> ConnectionInfo {
+        let mut info = ConnectionInfo::default();
+
+        for (key, value) in parse_connection_string(connection_string) {
+            match key.to_ascii_uppercase().as_str() {
+                "DRIVER" => info.driver = Some(value),
+                "DSN" => info.dsn = Some(value),
+                "SERVER" | "HOST" | "HOSTNAME" | "ADDRESS" | "ADDR" | "DATA SOURCE" => info.server = Some(value),
+                "DATABASE" | "DB" | "INITIAL CATALOG" => info.database = Some(value),
+                "UID" | "USER" | "USER ID" | "USERNAME" => info.user = Some(value),
+                "PWD" | "PASSWORD" => info.password = true,
+                _ => (),
+            }
+        }
+
+        info
+    }
+}
+
+impl fmt::Display for ConnectionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fields = [
+            ("Driver", &self.driver),
+            ("DSN", &self.dsn),
+            ("Server", &self.server),
+            ("Database", &self.database),
+            ("UID", &self.user),
+        ];
+
+        let mut first = true;
+        for (key, value) in fields.iter() {
+            if let Some(value) = value {
+                if !first {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}={}", key, value)?;
+                first = false;
+            }
+        }
+        if self.password {
+            if !first {
+                write!(f, " ")?;
+            }
+            write!(f, "PWD=***")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_string_builder() {
+        let connection_string = ConnectionStringBuilder::new()
+            .attribute("DSN", "foo")
+            .attribute("UID", "bar")
+            .attribute("PWD", "baz")
+            .build();
+
+        assert_eq!(connection_string, "DSN=foo;UID=bar;PWD=baz;");
+    }
+
+    #[test]
+    fn test_connection_string_builder_escaping() {
+        let connection_string = ConnectionStringBuilder::new()
+            .attribute("PWD", "a;b")
+            .attribute("Password2", "{x}")
+            .attribute("Key", "a=b")
+            .attribute("Space", " x ")
+            .build();
+
+        assert_eq!(connection_string, "PWD={a;b};Password2={{x}}};Key={a=b};Space={ x };");
+    }
+
+    #[test]
+    fn test_parse_connection_string() {
+        assert_eq!(
+            parse_connection_string("Driver={ODBC Driver 17 for SQL Server}; Server = localhost ;PWD={pa;ss}}word};UID=sa"),
+            [
+                ("Driver".to_owned(), "ODBC Driver 17 for SQL Server".to_owned()),
+                ("Server".to_owned(), "localhost".to_owned()),
+                ("PWD".to_owned(), "pa;ss}word".to_owned()),
+                ("UID".to_owned(), "sa".to_owned()),
+            ]
+        );
+        assert!(parse_connection_string("").is_empty());
+        assert!(parse_connection_string(";").is_empty());
+    }
+
+    #[test]
+    fn test_parse_connection_string_builder_round_trip() {
+        let connection_string = ConnectionStringBuilder::new()
+            .attribute("PWD", "{x};y")
+            .attribute("Space", " x ")
+            .build();
+
+        assert_eq!(
+            parse_connection_string(&connection_string),
+            [("PWD".to_owned(), "{x};y".to_owned()), ("Space".to_owned(), " x ".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_connection_info() {
+        let info = ConnectionInfo::from_connection_string("Driver={ODBC Driver 17 for SQL Server};Server=localhost;Database=foo;UID=sa;PWD=secret;");
+
+        assert_eq!(info.server.as_deref(), Some("localhost"));
+        assert_eq!(info.database.as_deref(), Some("foo"));
+        assert_eq!(info.user.as_deref(), Some("sa"));
+        assert!(info.password);
+        assert_eq!(info.to_string(), "Driver=ODBC Driver 17 for SQL Server Server=localhost Database=foo UID=sa PWD=***");
+        assert!(!format!("{:?}", info).contains("secret"));
+    }
+
+    #[test]
+    fn test_redact_connection_string() {
+        assert_eq!(
+            redact_connection_string("DRIVER={ODBC Driver 17 for SQL Server};SERVER=localhost;UID=sa;PWD={pa;ss}}word};"),
+            "DRIVER=ODBC Driver 17 for SQL Server;SERVER=localhost;UID=sa;PWD=***;"
+        );
+        assert_eq!(redact_connection_string("DSN=foo;Password=bar"), "DSN=foo;Password=***;");
+    }
+
+    #[test]
+    fn test_validate_connection_string() {
+        assert!(validate_connection_string("DRIVER={ODBC Driver 17 for SQL Server};SERVER=localhost;").is_ok());
+        assert!(validate_connection_string("dsn=foo").is_ok());
+        assert!(validate_connection_string("FileDSN=/etc/foo.dsn;UID=sa").is_ok());
+
+        assert_eq!(validate_connection_string(""), Err("validating connection string: connection string is empty"));
+        assert_eq!(validate_connection_string("  "), Err("validating connection string: connection string is empty"));
+        assert_eq!(validate_connection_string("localhost"), Err("validating connection string: expected KEY=value attributes separated with ';'"));
+        assert_eq!(validate_connection_string("SERVER=localhost;UID=sa"), Err("validating connection string: connection string missing DRIVER, DSN or FILEDSN attribute"));
+        assert_eq!(validate_connection_string("DSN=;SERVER=localhost"), Err("validating connection string: connection string missing DRIVER, DSN or FILEDSN attribute"));
+    }
+
+    #[test]
+    fn test_connection_string_builder_replace() {
+        let connection_string = ConnectionStringBuilder::new()
+            .attribute("UID", "foo")
+            .attribute("uid", "bar")
+            .build();
+
+        assert_eq!(connection_string, "UID=bar;");
+    }
+}
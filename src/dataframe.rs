@@ -0,0 +1,131 @@
+/*!
+Conversion of `ResultSet` into `polars::DataFrame` (with "polars" feature).
+!*/
+
+use polars::prelude::{DataFrame, NamedFrom, PolarsError, Series};
+use std::error::Error;
+use std::fmt;
+
+use crate::result_set::{DataAccessError, ResultSet};
+use crate::row::{Configuration, DatumType, TryFromRow};
+use crate::value::Value;
+use crate::value_row::ValueRow;
+
+/// Error converting `ResultSet` into `DataFrame`.
+#[derive(Debug)]
+pub enum DataFrameError {
+    DataAccessError(DataAccessError),
+    PolarsError(PolarsError),
+}
+
+impl fmt::Display for DataFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataFrameError::DataAccessError(_) => write!(f, "failed to access result data"),
+            DataFrameError::PolarsError(_) => write!(f, "failed to create data frame"),
+        }
+    }
+}
+
+impl Error for DataFrameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DataFrameError::DataAccessError(err) => Some(err),
+            DataFrameError::PolarsError(err) => Some(err),
+        }
+    }
+}
+
+impl From<DataAccessError> for DataFrameError {
+    fn from(err: DataAccessError) -> DataFrameError {
+        DataFrameError::DataAccessError(err)
+    }
+}
+
+impl From<PolarsError> for DataFrameError {
+    fn from(err: PolarsError) -> DataFrameError {
+        DataFrameError::PolarsError(err)
+    }
+}
+
+/// Column values collected with type corresponding to `DatumType`.
+enum ColumnData {
+    Boolean(Vec<Option<bool>>),
+    Int32(Vec<Option<i32>>),
+    Int64(Vec<Option<i64>>),
+    Float32(Vec<Option<f32>>),
+    Float64(Vec<Option<f64>>),
+    Utf8(Vec<Option<String>>),
+}
+
+impl ColumnData {
+    fn new(datum_type: DatumType) -> ColumnData {
+        match datum_type {
+            DatumType::Bit => ColumnData::Boolean(Vec::new()),
+            DatumType::Tinyint | DatumType::Smallint | DatumType::Integer => ColumnData::Int32(Vec::new()),
+            DatumType::Bigint => ColumnData::Int64(Vec::new()),
+            DatumType::Float => ColumnData::Float32(Vec::new()),
+            DatumType::Double => ColumnData::Float64(Vec::new()),
+            // decimal, date and time and JSON values are represented by their string form
+            _ => ColumnData::Utf8(Vec::new()),
+        }
+    }
+
+    fn push(&mut self, value: Option<Value>) {
+        match self {
+            ColumnData::Boolean(data) => data.push(value.and_then(|value| value.to_bool())),
+            ColumnData::Int32(data) => data.push(value.and_then(|value| {
+                value
+                    .to_i32()
+                    .or_else(|| value.to_i16().map(i32::from))
+                    .or_else(|| value.to_i8().map(i32::from))
+            })),
+            ColumnData::Int64(data) => data.push(value.and_then(|value| value.to_i64())),
+            ColumnData::Float32(data) => data.push(value.and_then(|value| value.to_f32())),
+            ColumnData::Float64(data) => data.push(value.and_then(|value| value.to_f64())),
+            ColumnData::Utf8(data) => data.push(value.map(|value| value.into_string().unwrap_or_else(|value| value.to_string()))),
+        }
+    }
+
+    fn into_series(self, name: &str) -> Series {
+        match self {
+            ColumnData::Boolean(data) => Series::new(name, &data),
+            ColumnData::Int32(data) => Series::new(name, &data),
+            ColumnData::Int64(data) => Series::new(name, &data),
+            ColumnData::Float32(data) => Series::new(name, &data),
+            ColumnData::Float64(data) => Series::new(name, &data),
+            ColumnData::Utf8(data) => Series::new(name, &data),
+        }
+    }
+}
+
+impl<'h, 'c: 'h, V, S, C: Configuration> ResultSet<'h, 'c, V, S, C>
+where
+    V: TryFromRow<C>,
+{
+    /// Fetch all remaining rows into `polars::DataFrame` with columns named and typed after the schema.
+    ///
+    /// `NULL` values are represented as Polars nulls. Columns of decimal, date/time and JSON types are represented as strings.
+    pub fn into_dataframe(mut self) -> Result<DataFrame, DataFrameError> {
+        let mut columns = self
+            .schema()
+            .iter()
+            .map(|column_type| ColumnData::new(column_type.datum_type))
+            .collect::<Vec<_>>();
+
+        let mut buf = ValueRow::new();
+        while self.fetch_into(&mut buf)? {
+            for (column, value) in columns.iter_mut().zip(buf.drain(..)) {
+                column.push(value);
+            }
+        }
+
+        let series = columns
+            .into_iter()
+            .zip(self.schema())
+            .map(|(column, column_type)| column.into_series(&column_type.name))
+            .collect::<Vec<_>>();
+
+        Ok(DataFrame::new(series)?)
+    }
+}
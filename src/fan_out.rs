@@ -0,0 +1,122 @@
+//! Running the same query against multiple databases (e.g. shards) and merging the results.
+
+use log::debug;
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::thread;
+
+use crate::query::QueryError;
+use crate::value_row::ValueRow;
+use crate::Odbc;
+
+// rows buffered per fan-out before database threads wait for the consumer
+const FAN_OUT_BUFFER: usize = 1024;
+
+/// Failure of single connection of fan-out query; other connections are not affected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanOutError {
+    /// Index of the connection string that failed.
+    pub source: usize,
+    /// Description of the error including its causes.
+    pub message: String,
+}
+
+impl fmt::Display for FanOutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fan-out query failed for connection {}: {}", self.source, self.message)
+    }
+}
+
+impl Error for FanOutError {}
+
+impl FanOutError {
+    fn new(source: usize, error: QueryError) -> FanOutError {
+        let mut message = error.to_string();
+        let mut cause = error.source();
+        while let Some(err) = cause {
+            message.push_str(": ");
+            message.push_str(&err.to_string());
+            cause = err.source();
+        }
+        FanOutError { source, message }
+    }
+}
+
+/// Stream of rows of fan-out query tagged with index of the connection string they came from.
+///
+/// Created with `fan_out()`.
+#[derive(Debug)]
+pub struct FanOut {
+    rows: Receiver<Result<(usize, ValueRow), FanOutError>>,
+}
+
+impl Iterator for FanOut {
+    type Item = Result<(usize, ValueRow), FanOutError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.recv().ok()
+    }
+}
+
+/// Run query against each of the databases on separate thread and merge the rows into single stream as they arrive.
+///
+/// Each row is tagged with index of the connection string it came from.
+/// Failure to connect, execute the query or fetch a row is reported as `FanOutError` item for that connection only and
+/// remaining connections continue to provide rows.
+/// Dropping the `FanOut` stops the threads after they try to provide next row.
+pub fn fan_out<S: AsRef<str>>(connection_strings: &[S], query: &str) -> FanOut {
+    let (sender, rows) = sync_channel(FAN_OUT_BUFFER);
+
+    for (source, connection_string) in connection_strings.iter().enumerate() {
+        let sender = sender.clone();
+        let connection_string = connection_string.as_ref().to_owned();
+        let query = query.to_owned();
+
+        thread::spawn(move || {
+            let result = Odbc::connect(&connection_string)
+                .map_err(QueryError::from)
+                .and_then(|mut connection| -> Result<(), QueryError> {
+                    for row in connection.handle().query::<ValueRow>(&query)? {
+                        let row = row.map(|row| (source, row)).map_err(|err| FanOutError::new(source, err.into()));
+                        if sender.send(row).is_err() {
+                            debug!("Fan-out consumer gone; stopping query of connection {}", source);
+                            break;
+                        }
+                    }
+                    Ok(())
+                });
+
+            if let Err(err) = result {
+                sender.send(Err(FanOutError::new(source, err))).ok();
+            }
+        });
+    }
+
+    FanOut { rows }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::Value;
+    #[allow(unused_imports)]
+    use assert_matches::assert_matches;
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_fan_out() {
+        let connection_string = crate::tests::sql_server_connection_string();
+        let rows = fan_out(&[connection_string.as_str(), "DSN=does-not-exist", connection_string.as_str()], "SELECT 42")
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows.len(), 3);
+        let mut sources = rows.iter().filter_map(|row| row.as_ref().ok()).map(|(source, _)| *source).collect::<Vec<_>>();
+        sources.sort();
+        assert_eq!(sources, [0, 2]);
+        assert!(rows.iter().filter_map(|row| row.as_ref().ok()).all(|(_, row)| row[0] == Some(Value::Integer(42))));
+        assert_matches!(rows.iter().find_map(|row| row.as_ref().err()), Some(FanOutError { source: 1, .. }));
+    }
+}
@@ -11,8 +11,11 @@ With this library you can:
     * vector of dynamically typed values,
 * create thread local connections for multithreaded applications.
 
+`DECIMAL`/`NUMERIC` (and SQL Server `MONEY`/`SMALLMONEY`) columns are read as `Decimal` with `rust_decimal` feature enabled or as exact `String` representation otherwise.
+`u64` values above `i64::MAX` (e.g. stored as `DECIMAL(20, 0)`) are converted from that exact representation without loss.
+Binary columns (e.g. SQL Server `ROWVERSION`/`TIMESTAMP` version stamps, which are not date/time values) are read as lower case hex `String`.
+
 Things still missing:
-* support for `DECIMAL` types - currently `DECIMAL` columns need to be cast to `DOUBLE` on the query (PR welcome),
 * rest of this list - please open issue in `GitHub` issue tracker for missing functionality, bugs, etc..
 
 Example usage
@@ -280,6 +283,7 @@ let connection_string = std::env::var("DB_CONNECTION_STRING")
 
 let mut connection = Odbc::connect_with_settings(&connection_string, Settings {
     utf_16_strings: true,
+    ..Default::default()
 }).expect("failed to connect to database");
 ```
 
@@ -293,6 +297,7 @@ let connection_string = std::env::var("DB_CONNECTION_STRING")
 
 let mut connection = Odbc::connect_with_settings(&connection_string, Settings {
     utf_16_strings: true,
+    ..Default::default()
 }).expect("failed to connect to database");
 
 let mut db = connection.handle();
@@ -314,6 +319,7 @@ let connection_string = std::env::var("DB_CONNECTION_STRING")
 
 let mut connection = Odbc::connect_with_settings(&connection_string, Settings {
     utf_16_strings: true,
+    ..Default::default()
 }).expect("failed to connect to database");
 
 let mut db = connection.handle();
@@ -336,11 +342,18 @@ ODBC statistics: connections: open: 5, queries: preparing: 2, executing: 1, fetc
 
 Note that they are not strongly synchronised so things may be observed counted twice.
 
+Tracing spans (with "tracing" feature)
+-------------
+
+If enabled, connect, prepare, execute and fetch operations are instrumented with `tracing` debug level spans
+(`odbc_connect`, `odbc_prepare`, `odbc_execute` and `odbc_fetch`) with fields like `query_hash`, `rows` and `duration_ms`.
+Query text itself is not recorded in spans; `log` macros are still used for logging.
+
 !*/
 
 use error_context::prelude::*;
 use lazy_static::lazy_static;
-use odbc::{DiagnosticRecord, DriverInfo, Environment, Version3};
+use odbc::{ffi, DiagnosticRecord, DriverInfo, Environment, Handle as OdbcHandle, Version3};
 use regex::Regex;
 use std::error::Error;
 use std::fmt;
@@ -354,6 +367,10 @@ pub use odbc::{Executed, Prepared};
 
 mod query;
 pub use query::*;
+mod query_builder;
+pub use query_builder::*;
+mod connection_string;
+pub use connection_string::*;
 mod result_set;
 pub use result_set::*;
 mod row;
@@ -363,17 +380,34 @@ pub use value::*;
 mod value_row;
 pub use value_row::*;
 mod stats;
+mod spans;
+mod attributes;
+#[cfg(feature = "polars")]
+mod dataframe;
+#[cfg(feature = "polars")]
+pub use dataframe::*;
 #[cfg(feature = "statistics")]
 pub use stats::statistics;
 
 pub mod odbc_type;
 pub mod thread_local;
+pub mod fan_out;
+pub mod script;
+#[cfg(feature = "tokio")]
+pub mod stream;
 
-pub use odbc_type::StringUtf16;
+pub use odbc_type::{Guid, StringUtf16};
 
 /// ODBC library initialization and connection errors.
 #[derive(Debug)]
-pub struct OdbcError(Option<DiagnosticRecord>, &'static str);
+pub struct OdbcError(Option<Diagnostic>, &'static str);
+
+/// Cause of `OdbcError` as reported by `odbc` crate or read from the handle after failed raw FFI call.
+#[derive(Debug)]
+pub(crate) enum Diagnostic {
+    Record(DiagnosticRecord),
+    Handle(DriverWarning),
+}
 
 impl fmt::Display for OdbcError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -381,8 +415,11 @@ impl fmt::Display for OdbcError {
     }
 }
 
-fn to_dyn(diag: &Option<DiagnosticRecord>) -> Option<&(dyn Error + 'static)> {
-    diag.as_ref().map(|e| e as &(dyn Error + 'static))
+fn to_dyn(diag: &Option<Diagnostic>) -> Option<&(dyn Error + 'static)> {
+    diag.as_ref().map(|diag| match diag {
+        Diagnostic::Record(record) => record as &(dyn Error + 'static),
+        Diagnostic::Handle(warning) => warning as &(dyn Error + 'static),
+    })
 }
 
 impl Error for OdbcError {
@@ -393,13 +430,13 @@ impl Error for OdbcError {
 
 impl From<ErrorContext<Option<DiagnosticRecord>, &'static str>> for OdbcError {
     fn from(err: ErrorContext<Option<DiagnosticRecord>, &'static str>) -> OdbcError {
-        OdbcError(err.error, err.context)
+        OdbcError(err.error.map(Diagnostic::Record), err.context)
     }
 }
 
 impl From<ErrorContext<DiagnosticRecord, &'static str>> for OdbcError {
     fn from(err: ErrorContext<DiagnosticRecord, &'static str>) -> OdbcError {
-        OdbcError(Some(err.error), err.context)
+        OdbcError(Some(Diagnostic::Record(err.error)), err.context)
     }
 }
 
@@ -424,6 +461,29 @@ lazy_static! {
 /// We need to allow mutable environment to be used to list drivers but only one environment should exist at the same time.
 static ODBC_INIT: AtomicBool = AtomicBool::new(false);
 
+/// Connection pooling scheme of ODBC Driver Manager (`SQL_ATTR_CONNECTION_POOLING`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionPooling {
+    /// Single pool of connections per driver (`SQL_CP_ONE_PER_DRIVER`).
+    OnePerDriver,
+    /// Single pool of connections per environment (`SQL_CP_ONE_PER_HENV`).
+    OnePerEnvironment,
+}
+
+/// How pooled connection is matched to new connection request (`SQL_ATTR_CP_MATCH`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoolMatch {
+    /// Only connections with exactly the same connection string and connection attributes are reused (`SQL_CP_STRICT_MATCH`).
+    Strict,
+    /// Connections with matching connection string keywords are reused even if some attributes differ (`SQL_CP_RELAXED_MATCH`).
+    Relaxed,
+}
+
+const SQL_CP_ONE_PER_DRIVER: ffi::SQLUINTEGER = 1;
+const SQL_CP_ONE_PER_HENV: ffi::SQLUINTEGER = 2;
+const SQL_CP_STRICT_MATCH: ffi::SQLUINTEGER = 0;
+const SQL_CP_RELAXED_MATCH: ffi::SQLUINTEGER = 1;
+
 impl fmt::Debug for Odbc {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("Odbc").field("version", &3).finish()
@@ -450,6 +510,49 @@ impl Odbc {
         lazy_static::initialize(&ODBC);
     }
 
+    /// Enable connection pooling of ODBC Driver Manager and initialize global static ODBC environment now.
+    ///
+    /// Physical connections are kept open by the Driver Manager after `Connection` is dropped and reused by following
+    /// connect calls with matching connection string according to `matching`.
+    /// Pooling is process wide setting that has to be enabled before the environment is created so this fails if it was
+    /// already initialized (e.g. with `Odbc::initialize()` or by connecting to a database).
+    /// Some Driver Managers need pooling to be enabled in their configuration as well (e.g. `Pooling = Yes` in unixODBC
+    /// `odbcinst.ini`).
+    ///
+    /// Connections of `thread_local` module are made with the same environment so they are pooled as well but note that they
+    /// are only returned to the pool when dropped by the closure or when their thread exits.
+    pub fn initialize_with_pooling(pooling: ConnectionPooling, matching: PoolMatch) -> Result<(), OdbcError> {
+        if ODBC_INIT.load(atomic::Ordering::SeqCst) {
+            return Err(OdbcError(None, "enabling connection pooling: ODBC environment already initialised"))
+        }
+
+        let pooling = match pooling {
+            ConnectionPooling::OnePerDriver => SQL_CP_ONE_PER_DRIVER,
+            ConnectionPooling::OnePerEnvironment => SQL_CP_ONE_PER_HENV,
+        };
+        attributes::set_environment_attribute_integer(
+            std::ptr::null_mut(),
+            ffi::SQL_ATTR_CONNECTION_POOLING,
+            pooling,
+            "enabling connection pooling",
+        )?;
+
+        Odbc::initialize();
+
+        let matching = match matching {
+            PoolMatch::Strict => SQL_CP_STRICT_MATCH,
+            PoolMatch::Relaxed => SQL_CP_RELAXED_MATCH,
+        };
+        attributes::set_environment_attribute_integer(
+            unsafe { ODBC.environment.handle() },
+            ffi::SQL_ATTR_CP_MATCH,
+            matching,
+            "setting connection pool matching",
+        )?;
+
+        Ok(())
+    }
+
     /// Provides list of `DriverInfo` structures describing available ODBC drivers.
     /// This will panic if ODBC was already initialized by `Odbc::connect()` or `Odbc::initialize()`.
     pub fn list_drivers() -> Result<Vec<DriverInfo>, OdbcError> {
@@ -497,6 +600,17 @@ impl Odbc {
     ) -> Result<Connection, OdbcError> {
         Connection::with_settings_concurrent(&ODBC, connection_string, settings)
     }
+
+    /// Connect to database letting the driver complete (or prompt for) missing connection string attributes.
+    /// Returns the connection and completed connection string; see `Connection::connect_with_prompt()`.
+    pub fn connect_with_prompt(
+        connection_string: &str,
+        completion: DriverCompletion,
+        window_handle: odbc::ffi::SQLHWND,
+        settings: Settings,
+    ) -> Result<(Connection, String), OdbcError> {
+        Connection::connect_with_prompt(&ODBC, connection_string, completion, window_handle, settings)
+    }
 }
 
 /// Error splitting SQL script into single queries.
@@ -512,16 +626,50 @@ impl fmt::Display for SplitQueriesError {
 impl Error for SplitQueriesError {}
 
 /// Split SQL script into list of queries.
-/// Each query needs to be terminated with semicolon (";").
+/// Each query needs to be terminated with semicolon (";") except for the last one.
 /// Lines starting with two dashes ("--") are skipped.
+/// ODBC escape sequences (e.g. `{d '2020-01-01'}` or `{fn UCASE(name)}`) are passed through unchanged.
 pub fn split_queries(queries: &str) -> impl Iterator<Item = Result<&str, SplitQueriesError>> {
     lazy_static! {
         // https://regex101.com/r/6YTuVG/4
-        static ref RE: Regex = Regex::new(r#"(?:[\t \n]|--.*\n|!.*\n)*((?:[^;"']+(?:'(?:[^'\\]*(?:\\.)?)*')?(?:"(?:[^"\\]*(?:\\.)?)*")?)*;) *"#).unwrap();
+        static ref RE: Regex = Regex::new(r#"^(?:[\t \r\n]|--.*\n|!.*\n)*((?:[^;"']+(?:'(?:[^'\\]*(?:\\.)?)*')?(?:"(?:[^"\\]*(?:\\.)?)*")?)*;) *"#).unwrap();
+        // white space and comments before not terminated last query
+        static ref TAIL_RE: Regex = Regex::new(r#"^(?:[\t \r\n]|--.*|!.*)*"#).unwrap();
+    }
+
+    let mut rest = queries;
+    let mut done = false;
+
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+
+        if let Some(c) = RE.captures(rest) {
+            let query = c.get(1).ok_or(SplitQueriesError).map(|m| m.as_str());
+            rest = &rest[c.get(0).map(|m| m.end()).unwrap_or_else(|| rest.len())..];
+            return Some(query);
+        }
+        done = true;
+
+        let skip = TAIL_RE.find(rest).map(|m| m.end()).unwrap_or(0);
+        let tail = rest[skip..].trim_end();
+        if tail.is_empty() {
+            None
+        } else {
+            Some(Ok(tail))
+        }
+    })
+}
+
+/// Count `?` parameter placeholders of the query that are not within quoted strings or comments (with the same quoting
+/// rules as `split_queries()`).
+pub(crate) fn count_placeholders(query: &str) -> usize {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r#"'(?:[^'\\]*(?:\\.)?)*'|"(?:[^"\\]*(?:\\.)?)*"|--.*|\?"#).unwrap();
     }
-    RE.captures_iter(queries)
-        .map(|c| c.get(1).ok_or(SplitQueriesError))
-        .map(|r| r.map(|m| m.as_str()))
+
+    RE.find_iter(query).filter(|m| m.as_str() == "?").count()
 }
 
 #[cfg(test)]
@@ -796,6 +944,38 @@ pub mod tests {
         assert_matches!(data[0][0], Some(Value::Decimal(ref dec)) => assert_eq!(dec.to_string(), "10.9231213232423424324"));
     }
 
+    #[cfg(all(feature = "test-sql-server", feature = "rust_decimal"))]
+    #[test]
+    fn test_sql_server_types_money() {
+        let mut connection = connect_sql_server();
+
+        let data = connection
+            .handle()
+            .query::<ValueRow>("SELECT CAST(12345.6789 AS MONEY), CAST(-1234.5678 AS SMALLMONEY)")
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::Decimal(ref dec)) => assert_eq!(dec.to_string(), "12345.6789"));
+        assert_matches!(data[0][1], Some(Value::Decimal(ref dec)) => assert_eq!(dec.to_string(), "-1234.5678"));
+    }
+
+    #[cfg(all(feature = "test-sql-server", not(feature = "rust_decimal")))]
+    #[test]
+    fn test_sql_server_types_money_string() {
+        let mut connection = connect_sql_server();
+
+        let data = connection
+            .handle()
+            .query::<ValueRow>("SELECT CAST(12345.6789 AS MONEY), CAST(-1234.5678 AS SMALLMONEY)")
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, "12345.6789"));
+        assert_matches!(data[0][1], Some(Value::String(ref string)) => assert_eq!(string, "-1234.5678"));
+    }
+
     #[cfg(feature = "test-hive")]
     #[test]
     fn test_hive_types_null() {
@@ -827,6 +1007,23 @@ pub mod tests {
         assert!(data.len() > 0);
     }
 
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_list_catalogs_schemas_table_types() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let catalogs = db.list_catalogs().expect("list catalogs");
+        assert!(catalogs.iter().any(|catalog| catalog == "master"));
+
+        let schemas = db.list_schemas().expect("list schemas");
+        assert!(schemas.iter().any(|schema| schema == "dbo"));
+
+        let table_types = db.list_table_types().expect("list table types");
+        assert!(table_types.iter().any(|table_type| table_type == "TABLE"));
+        assert!(table_types.iter().any(|table_type| table_type == "VIEW"));
+    }
+
     #[cfg(feature = "chrono")]
     #[cfg(feature = "test-sql-server")]
     #[test]
@@ -859,6 +1056,40 @@ pub mod tests {
         assert_matches!(&data[0][0], Some(time @ Value::Time(_)) => assert_eq!(&time.to_naive_time().unwrap().to_string(), "10:22:33.765432100"));
     }
 
+    #[cfg(feature = "chrono")]
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_bind_date_time() {
+        use chrono::{FixedOffset, TimeZone, Utc};
+        use odbc_type::{OffsetTimestamp, UnixTimestamp};
+
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        db.query::<()>("CREATE TABLE #odbc_iter_date_time (utc_value DATETIME2(6), offset_value DATETIMEOFFSET(6))")
+            .expect("failed to create table")
+            .no_result()
+            .unwrap();
+
+        let date_time = FixedOffset::east(2 * 3600).ymd(2019, 1, 10).and_hms_micro(12, 17, 40, 229123);
+        let utc: UnixTimestamp = date_time.with_timezone(&Utc).into();
+        let offset: OffsetTimestamp = date_time.into();
+
+        db.query_with_parameters::<(), _>("INSERT INTO #odbc_iter_date_time VALUES (?, ?)", |q| q.bind(&utc)?.bind(&offset))
+            .expect("failed to insert")
+            .no_result()
+            .unwrap();
+
+        let (utc, offset) = db
+            .query::<(NaiveDateTime, String)>("SELECT utc_value, offset_value FROM #odbc_iter_date_time")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(utc, date_time.naive_utc());
+        assert_eq!(offset, "2019-01-10T12:17:40.229123+02:00");
+    }
+
     #[cfg(feature = "test-sql-server")]
     #[test]
     fn test_sql_server_affected_rows_query() {
@@ -925,6 +1156,21 @@ pub mod tests {
         assert_eq!(value.to_i32().unwrap(), 42);
     }
 
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_query_with_bind_params() {
+        let mut connection = connect_sql_server();
+
+        let data: (i64, String, f64) = connection
+            .handle()
+            .query_with_parameters("SELECT ?, ?, ?", |q| q.bind_params((&42i64, &"foo", &1.5f64)))
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(data, (42, "foo".to_owned(), 1.5));
+    }
+
     #[cfg(feature = "test-sql-server")]
     #[test]
     fn test_sql_server_query_with_many_parameters() {
@@ -934,9 +1180,7 @@ pub mod tests {
 
         let data: Vec<ValueRow> = connection
             .handle()
-            .query_with_parameters("SELECT ?, ?, ?, ? AS val;", |q| {
-                val.iter().fold(Ok(q), |q, v| q.and_then(|q| q.bind(v)))
-            })
+            .query_with_parameters("SELECT ?, ?, ?, ? AS val;", |q| q.bind_all(&val))
             .expect("failed to run query")
             .collect::<Result<_, _>>()
             .expect("fetch data");
@@ -961,9 +1205,7 @@ pub mod tests {
             .expect("prepare statement");
 
         let data: Vec<ValueRow> = handle
-            .execute_with_parameters(statement, |q| {
-                val.iter().fold(Ok(q), |q, v| q.and_then(|q| q.bind(v)))
-            })
+            .execute_with_parameters(statement, |q| q.bind_all(&val))
             .expect("failed to run query")
             .collect::<Result<_, _>>()
             .expect("fetch data");
@@ -1003,179 +1245,1660 @@ pub mod tests {
         assert_eq!(schema[1].datum_type, DatumType::Integer);
     }
 
-    #[cfg(feature = "test-hive")]
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_hive_empty_data_set() {
-        let mut hive = connect_hive();
+    fn test_sql_server_prepare_described() {
+        let mut connection = connect_sql_server();
+        let mut handle = connection.handle();
 
-        let data = hive
-            .handle()
-            .query::<ValueRow>("USE default;")
-            .expect("failed to run query")
-            .collect::<Result<Vec<_>, _>>()
-            .expect("fetch data");
+        let statement = handle
+            .prepare("SELECT CAST(? as INTEGER) as foo;")
+            .expect("prepare statement");
+        assert!(statement.param_schema().is_none());
+        assert!(statement.result_schema().is_none());
 
-        assert!(data.is_empty());
+        let statement = handle
+            .prepare_described("SELECT CAST(? as INTEGER) as foo;")
+            .expect("prepare statement");
+
+        let param_schema = statement.param_schema().expect("param schema");
+        assert_eq!(param_schema.len(), 1);
+        assert_eq!(param_schema[0].decimal_digits, Some(0));
+        let result_schema = statement.result_schema().expect("result schema");
+        assert_eq!(result_schema.len(), 1);
+        assert_eq!(result_schema[0].datum_type, DatumType::Integer);
+
+        let result_set = handle
+            .execute_with_parameters::<i32, _>(statement, |q| q.bind(&42))
+            .expect("failed to execute");
+        let statement = result_set.close().expect("close");
+
+        assert_eq!(statement.param_schema().map(<[_]>::len), Some(1));
+        assert_eq!(statement.result_schema().map(|schema| schema[0].name.as_str()), Some("foo"));
+        assert_eq!(statement.query(), "SELECT CAST(? as INTEGER) as foo;");
     }
 
-    #[cfg(feature = "test-hive")]
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_hive_long_string_fetch_utf_8() {
-        let mut hive = connect_hive();
-
-        let data = hive
-            .handle()
-            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
-            .expect("failed to run query")
-            .collect::<Result<Vec<_>, _>>()
-            .expect("fetch data");
+    fn test_sql_server_ping() {
+        let mut connection = connect_sql_server();
 
-        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+        connection.handle().ping().expect("ping");
     }
 
-    #[cfg(feature = "test-monetdb")]
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_moentdb_long_string_fetch_utf_8() {
-        let mut monetdb = crate::tests::connect_monetdb();
+    fn test_sql_server_query_builder() {
+        let mut connection = connect_sql_server();
 
-        let data = monetdb
+        let mut query = QueryBuilder::new();
+        query
+            .push_sql("SELECT ")
+            .push_param(42)
+            .push_sql(" AS foo, ")
+            .push_param("bar".to_owned())
+            .push_sql(" AS bar WHERE 1 IN (")
+            .push_params(vec![1, 2, 3])
+            .push_sql(")");
+
+        let data: (i32, String) = connection
             .handle()
-            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
+            .query_with_parameters(query.sql(), |q| query.bind(q))
             .expect("failed to run query")
-            .collect::<Result<Vec<_>, _>>()
+            .single()
             .expect("fetch data");
 
-        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+        assert_eq!(data, (42, "bar".to_owned()));
     }
 
     #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_sql_server_long_string_fetch_utf_16_bind() {
-        let mut connection = connect_sql_server_with_settings(Settings {
-            utf_16_strings: true,
-        });
-
-        let utf_16_string = LONG_STRING.encode_utf16().collect::<Vec<u16>>();
+    fn test_sql_server_trim_char() {
+        let mut connection = connect_sql_server();
 
-        let mut handle = connection.handle();
+        let data: (String, String, String) = connection
+            .handle()
+            .query("SELECT CAST('foo' AS CHAR(6)), CAST(N'bar' AS NCHAR(6)), CAST('baz  ' AS VARCHAR(6))")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data, ("foo   ".to_owned(), "bar   ".to_owned(), "baz  ".to_owned()));
 
-        let statement = handle
-            .prepare("SELECT ? AS val;")
-            .expect("prepare statement");
+        let mut connection = connect_sql_server_with_settings(Settings {
+            trim_char: true,
+            ..Default::default()
+        });
 
-        let data: Vec<ValueRow> = handle
-            .execute_with_parameters(statement, |q| q.bind(&utf_16_string))
+        let data: (String, String, String) = connection
+            .handle()
+            .query("SELECT CAST('foo' AS CHAR(6)), CAST(N'bar' AS NCHAR(6)), CAST('baz  ' AS VARCHAR(6))")
             .expect("failed to run query")
-            .collect::<Result<Vec<_>, _>>()
+            .single()
             .expect("fetch data");
-
-        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+        assert_eq!(data, ("foo".to_owned(), "bar".to_owned(), "baz  ".to_owned()));
     }
 
     #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_sql_server_long_string_fetch_utf_16_bind_string_utf_16() {
+    fn test_sql_server_utf_16_lossy() {
         let mut connection = connect_sql_server_with_settings(Settings {
             utf_16_strings: true,
+            ..Default::default()
         });
 
-        let utf_16_string = StringUtf16::from(LONG_STRING);
-
-        let mut handle = connection.handle();
+        // lone high surrogate
+        assert!(connection
+            .handle()
+            .query::<String>("SELECT N'foo' + NCHAR(0xD800)")
+            .expect("failed to run query")
+            .single()
+            .is_err());
 
-        let statement = handle
-            .prepare("SELECT ? AS val;")
-            .expect("prepare statement");
+        let mut connection = connect_sql_server_with_settings(Settings {
+            utf_16_strings: true,
+            utf_16_lossy: true,
+            ..Default::default()
+        });
 
-        let data: Vec<ValueRow> = handle
-            .execute_with_parameters(statement, |q| q.bind(&utf_16_string))
+        let data = connection
+            .handle()
+            .query::<String>("SELECT N'foo' + NCHAR(0xD800)")
             .expect("failed to run query")
-            .collect::<Result<Vec<_>, _>>()
+            .single()
             .expect("fetch data");
 
-        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+        assert_eq!(data, "foo\u{FFFD}");
     }
 
-    #[cfg(feature = "test-hive")]
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_hive_long_string_fetch_utf_16() {
-        let mut hive = connect_hive_with_settings(Settings {
-            utf_16_strings: true,
-        });
+    fn test_sql_server_wide_empty_vs_null() {
+        for utf_16_strings in &[true, false] {
+            let mut connection = connect_sql_server_with_settings(Settings {
+                utf_16_strings: *utf_16_strings,
+                ..Default::default()
+            });
+
+            let data: Vec<Option<String>> = connection
+                .handle()
+                .query::<ValueRow>(
+                    "SELECT CAST(N'' AS NVARCHAR(10)), CAST(NULL AS NVARCHAR(10)), \
+                    CAST(N'' AS NVARCHAR(MAX)), CAST(NULL AS NVARCHAR(MAX)), \
+                    CAST(N'' AS NTEXT), CAST(NULL AS NTEXT)",
+                )
+                .expect("failed to run query")
+                .single()
+                .expect("fetch data")
+                .into_iter()
+                .map(|value| value.map(|value| value.into_string().expect("string value")))
+                .collect();
+
+            assert_eq!(
+                data,
+                [Some(String::new()), None, Some(String::new()), None, Some(String::new()), None],
+                "utf_16_strings: {}",
+                utf_16_strings
+            );
+        }
+    }
 
-        let data = hive
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_result_sets() {
+        let mut connection = connect_sql_server();
+
+        let items = connection
             .handle()
-            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
+            .query::<ValueRow>("SELECT 1 AS foo UNION SELECT 2; SELECT 'bar' AS bar, 3 AS baz")
             .expect("failed to run query")
+            .result_sets()
             .collect::<Result<Vec<_>, _>>()
             .expect("fetch data");
 
-        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+        assert_eq!(items.len(), 5);
+        assert_matches!(items[0], ResultSetItem::NewResultSet(ref schema) => assert_eq!(schema[0].name, "foo"));
+        assert_matches!(items[1], ResultSetItem::Row(ref row) => assert_matches!(row[0], Some(Value::Integer(1))));
+        assert_matches!(items[2], ResultSetItem::Row(ref row) => assert_matches!(row[0], Some(Value::Integer(2))));
+        assert_matches!(items[3], ResultSetItem::NewResultSet(ref schema) => assert_eq!(schema.len(), 2));
+        assert_matches!(items[4], ResultSetItem::Row(ref row) => {
+            assert_matches!(row[0], Some(Value::String(ref string)) => assert_eq!(string, "bar"));
+            assert_matches!(row[1], Some(Value::Integer(3)));
+        });
     }
 
-    #[cfg(feature = "test-monetdb")]
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_moentdb_long_string_fetch_utf_16() {
-        let mut monetdb = connect_monetdb_with_settings(Settings {
-            utf_16_strings: true,
-        });
+    fn test_sql_server_current_catalog() {
+        let mut connection = connect_sql_server();
 
-        let data = monetdb
+        connection.set_current_catalog("tempdb").expect("set current catalog");
+        assert_eq!(connection.current_catalog().expect("current catalog"), "tempdb");
+
+        let catalog: String = connection
             .handle()
-            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
+            .query("SELECT DB_NAME()")
             .expect("failed to run query")
-            .collect::<Result<Vec<_>, _>>()
+            .single()
             .expect("fetch data");
+        assert_eq!(catalog, "tempdb");
 
-        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+        assert!(connection.set_current_catalog("odbc_iter_does_not_exist").is_err());
     }
 
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_split_queries() {
-        let queries = split_queries(
-            r#"-- Foo
----
-CREATE DATABASE IF NOT EXISTS daily_reports;
-USE daily_reports;
+    fn test_sql_server_optional_scalar() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
 
-SELECT *;"#,
-        )
-        .collect::<Result<Vec<_>, _>>()
-        .expect("failed to parse");
-        assert_eq!(
-            queries,
-            [
-                "CREATE DATABASE IF NOT EXISTS daily_reports;",
-                "USE daily_reports;",
-                "SELECT *;"
-            ]
+        assert_matches!(db.query::<i32>("SELECT 1 WHERE 1 = 0").expect("failed to run query").optional(), Ok(None));
+        assert_matches!(db.query::<i32>("SELECT 1").expect("failed to run query").optional(), Ok(Some(1)));
+        assert_matches!(
+            db.query::<i32>("SELECT 1 UNION SELECT 2").expect("failed to run query").optional(),
+            Err(DataAccessError::UnexpectedMultipleRows)
+        );
+
+        assert_matches!(db.query::<i32>("SELECT 42").expect("failed to run query").scalar(), Ok(Some(42)));
+        assert_matches!(
+            db.query::<(i32, i32)>("SELECT 1, 2").expect("failed to run query").scalar(),
+            Err(DataAccessError::UnexpectedNumberOfColumns { expected: 1, got: 2 })
         );
     }
 
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_split_queries_end_white() {
-        let queries = split_queries(
-            r#"USE daily_reports;
-SELECT *;
+    fn test_sql_server_warnings() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
 
-"#,
-        )
-        .collect::<Result<Vec<_>, _>>()
-        .expect("failed to parse");
-        assert_eq!(queries, ["USE daily_reports;", "SELECT *;"]);
-    }
+        let mut result_set = db
+            .query::<Option<i32>>("SELECT SUM(x) FROM (VALUES (1), (NULL)) AS t(x)")
+            .expect("failed to run query");
 
-    #[test]
-    fn test_split_queries_simple() {
-        let queries = split_queries("SELECT 42;\nSELECT 24;\nSELECT 'foo';")
-            .collect::<Result<Vec<_>, _>>()
-            .expect("failed to parse");
-        assert_eq!(queries, ["SELECT 42;", "SELECT 24;", "SELECT 'foo';"]);
+        assert_matches!(result_set.next(), Some(Ok(Some(1))));
+        assert!(result_set.warnings().iter().any(|warning| warning.state == "01003"));
     }
 
+    #[cfg(feature = "test-sql-server")]
     #[test]
-    fn test_split_queries_semicolon() {
-        let queries = split_queries("SELECT 'foo; bar';\nSELECT 1;")
+    fn test_sql_server_warnings_not_last_column() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            read_as: vec![(odbc::ffi::SqlDataType::SQL_DECIMAL, DatumType::Integer)],
+            ..Default::default()
+        });
+        let mut db = connection.handle();
+
+        // DECIMAL read as integer by the driver is truncated with "01S07" warning that following column read would clear
+        let mut result_set = db
+            .query::<(i32, String)>("SELECT CAST(1.5 AS DECIMAL(10, 2)), 'foo'")
+            .expect("failed to run query");
+
+        assert_matches!(result_set.next(), Some(Ok((1, ref string))) => assert_eq!(string, "foo"));
+        assert!(result_set.warnings().iter().any(|warning| warning.state == "01S07"));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_fetch_into() {
+        let mut connection = connect_sql_server();
+
+        let mut result_set = connection
+            .handle()
+            .query::<ValueRow>("SELECT 1, 'foo' UNION SELECT 2, 'bar'")
+            .expect("failed to run query");
+
+        let mut buf = ValueRow::new();
+        let mut ids = Vec::new();
+        while result_set.fetch_into(&mut buf).expect("fetch data") {
+            assert_eq!(buf.len(), 2);
+            ids.push(buf[0].as_ref().and_then(Value::to_i32).expect("id"));
+        }
+
+        assert_eq!(ids, [1, 2]);
+        assert!(buf.is_empty());
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_fetch_ref() {
+        let mut connection = connect_sql_server();
+
+        let mut result_set = connection
+            .handle()
+            .query::<ValueRow>("SELECT 1, 'foo' UNION SELECT 2, 'bar'")
+            .expect("failed to run query");
+
+        let mut buf = ValueRow::new();
+        let mut names = Vec::new();
+        while let Some(row) = result_set.fetch_ref::<&[Option<Value>]>(&mut buf).expect("fetch data") {
+            names.push(row[1].as_ref().and_then(Value::as_str).expect("name").to_owned());
+        }
+
+        assert_eq!(names, ["foo", "bar"]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_rows_fetched() {
+        let mut connection = connect_sql_server();
+
+        let mut result_set = connection
+            .handle()
+            .query::<i32>("SELECT 1 UNION SELECT 2 UNION SELECT 3")
+            .expect("failed to run query");
+
+        assert_eq!(result_set.rows_fetched(), 0);
+        result_set.next().expect("row").expect("fetch data");
+        assert_eq!(result_set.rows_fetched(), 1);
+        assert_eq!(result_set.by_ref().count(), 2);
+        assert_eq!(result_set.rows_fetched(), 3);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_cursor_type() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            cursor_type: CursorType::Static,
+            concurrency: Concurrency::ReadOnly,
+            ..Default::default()
+        });
+
+        let data = connection
+            .handle()
+            .query::<i32>("SELECT 1 UNION SELECT 2")
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_eq!(data, [1, 2]);
+    }
+
+    #[cfg(all(feature = "test-sql-server", feature = "polars"))]
+    #[test]
+    fn test_sql_server_into_dataframe() {
+        let mut connection = connect_sql_server();
+
+        let df = connection
+            .handle()
+            .query::<ValueRow>("SELECT 1 AS foo, 'bar' AS bar UNION SELECT NULL, 'baz'")
+            .expect("failed to run query")
+            .into_dataframe()
+            .expect("fetch data");
+
+        assert_eq!(df.shape(), (2, 2));
+        assert_eq!(df.get_column_names(), ["foo", "bar"]);
+        assert_eq!(df.column("foo").expect("column").null_count(), 1);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_connection_info() {
+        let connection = connect_sql_server();
+
+        let info = connection.connection_info();
+        assert!(info.server.is_some() || info.dsn.is_some());
+        assert!(!info.to_string().contains("PWD=") || info.to_string().contains("PWD=***"));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_big_u64() {
+        let mut connection = connect_sql_server();
+
+        let data: u64 = connection
+            .handle()
+            .query("SELECT CAST(18446744073709551615 AS DECIMAL(20, 0))")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data, std::u64::MAX);
+
+        let data: ValueRow = connection
+            .handle()
+            .query("SELECT CAST(18446744073709551615 AS DECIMAL(20, 0))")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(u64::try_from_value(data[0].clone()).expect("u64 value"), std::u64::MAX);
+
+        let data: u64 = connection
+            .handle()
+            .query("SELECT CAST(5 AS DECIMAL(20, 2))")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data, 5);
+
+        assert_matches!(
+            connection
+                .handle()
+                .query::<u64>("SELECT CAST(5.5 AS DECIMAL(20, 2))")
+                .expect("failed to run query")
+                .single(),
+            Err(DataAccessError::FromRowError(_))
+        );
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_packet_size() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            packet_size: Some(16384),
+            ..Default::default()
+        });
+
+        let data: u32 = connection
+            .handle()
+            .query("SELECT 42")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data, 42);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_execution_time() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            slow_query_threshold: Some(std::time::Duration::from_millis(100)),
+            ..Default::default()
+        });
+
+        let result_set = connection
+            .handle()
+            .query::<ValueRow>("WAITFOR DELAY '00:00:00.200'; SELECT 42")
+            .expect("failed to run query");
+
+        assert!(result_set.execution_time() >= std::time::Duration::from_millis(200));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_disconnect() {
+        let mut connection = connect_sql_server();
+
+        connection.handle().ping().expect("ping");
+        connection.disconnect().expect("disconnect");
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_bind_bool() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        db.query::<ValueRow>("CREATE TABLE #bits (id INT, b BIT)")
+            .expect("failed to create table")
+            .no_result()
+            .expect("no result");
+        db.query_with_parameters::<ValueRow, _>("INSERT INTO #bits VALUES (1, ?), (2, ?)", |q| q.bind(&true)?.bind(&false))
+            .expect("failed to insert")
+            .no_result()
+            .expect("no result");
+
+        let data: Vec<bool> = db
+            .query("SELECT b FROM #bits ORDER BY id")
+            .expect("failed to run query")
+            .collect::<Result<_, _>>()
+            .expect("fetch data");
+
+        assert_eq!(data, [true, false]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_had_result_set() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let result_set = db
+            .query::<ValueRow>("SELECT 42 WHERE 1 = 0")
+            .expect("failed to run query");
+        assert!(result_set.had_result_set());
+        assert!(result_set.no_result().is_ok());
+
+        let result_set = db
+            .query::<ValueRow>("CREATE TABLE #had_result_set (id INT)")
+            .expect("failed to run query");
+        assert!(!result_set.had_result_set());
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_strict_nullability() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            strict_nullability: true,
+            ..Default::default()
+        });
+
+        let result_set = connection
+            .handle()
+            .query::<(Option<i32>, i32)>("SELECT CAST(NULL AS INT) AS a, 42 AS b")
+            .expect("failed to run query");
+        assert!(result_set.schema()[0].nullable);
+        assert!(!result_set.schema()[1].nullable);
+
+        let data = result_set.single().expect("fetch data");
+        assert_eq!(data, (None, 42));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_drop_partially_consumed() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let mut result_set = db
+            .query::<i32>("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3")
+            .expect("failed to run query");
+        assert_eq!(result_set.next().expect("row").expect("fetch data"), 1);
+        drop(result_set);
+
+        let data: i32 = db
+            .query("SELECT 42")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data, 42);
+
+        let statement = db.prepare("SELECT 1 UNION ALL SELECT 2").expect("prepare");
+        let mut result_set = db.execute::<i32>(statement).expect("failed to execute");
+        assert_eq!(result_set.next().expect("row").expect("fetch data"), 1);
+        drop(result_set);
+
+        let data: i32 = db
+            .query("SELECT 24")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data, 24);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_datetimeoffset() {
+        let mut connection = connect_sql_server();
+
+        let data: ValueRow = connection
+            .handle()
+            .query("SELECT CAST('2018-08-24 10:22:33 +02:00' AS DATETIMEOFFSET), CAST('2018-08-24 10:22:33.5 -05:30' AS DATETIMEOFFSET(3))")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_matches!(data[0], Some(Value::String(ref string)) => assert_eq!(string, "2018-08-24T10:22:33+02:00"));
+        assert_matches!(data[1], Some(Value::String(ref string)) => assert_eq!(string, "2018-08-24T10:22:33.5-05:30"));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_name_unnamed_columns() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            name_unnamed_columns: true,
+            ..Default::default()
+        });
+
+        let result_set = connection
+            .handle()
+            .query::<ValueRow>("SELECT 42, 24 AS foo, 1")
+            .expect("failed to run query");
+
+        let names: Vec<&str> = result_set.schema().iter().map(|column_type| column_type.name.as_str()).collect();
+        assert_eq!(names, ["col_1", "foo", "col_3"]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_rowversion() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        db.query::<ValueRow>("CREATE TABLE #versioned (id INT, version ROWVERSION)")
+            .expect("failed to create table")
+            .no_result()
+            .expect("no result");
+        db.query::<ValueRow>("INSERT INTO #versioned (id) VALUES (1)")
+            .expect("failed to insert")
+            .no_result()
+            .expect("no result");
+        db.query::<ValueRow>("INSERT INTO #versioned (id) VALUES (2)")
+            .expect("failed to insert")
+            .no_result()
+            .expect("no result");
+
+        let data: Vec<String> = db
+            .query("SELECT version FROM #versioned ORDER BY id")
+            .expect("failed to run query")
+            .collect::<Result<_, _>>()
+            .expect("fetch data");
+
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].len(), 16);
+        assert!(data[0] < data[1]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_vendor_type() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            vendor_types: vec![VendorType {
+                sql_type: odbc::ffi::SqlDataType::SQL_SS_XML,
+                type_name: "xml".to_owned(),
+                decoder: |data| Ok(Value::String(data.replace("foo", "bar"))),
+            }],
+            ..Default::default()
+        });
+
+        let data: ValueRow = connection
+            .handle()
+            .query("SELECT CAST('<foo/>' AS XML)")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_matches!(data[0], Some(Value::String(ref string)) => assert_eq!(string, "<bar/>"));
+    }
+
+    #[cfg(all(feature = "test-sql-server", feature = "serde_json"))]
+    #[test]
+    fn test_sql_server_json_columns() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            json_columns: vec!["doc".to_owned(), "DOC2".to_owned()],
+            ..Default::default()
+        });
+
+        let result_set = connection
+            .handle()
+            .query::<ValueRow>("SELECT N'{\"a\": [1, 2]}' AS doc, N'not json' AS doc2, N'{}' AS other")
+            .expect("failed to run query");
+        assert_eq!(result_set.schema()[0].datum_type, DatumType::Json);
+        assert_eq!(result_set.schema()[2].datum_type, DatumType::String);
+
+        let data = result_set.single().expect("fetch data");
+        assert_matches!(data[0], Some(Value::Json(ref json)) => assert_eq!(json, &serde_json::json!({"a": [1, 2]})));
+        assert_matches!(data[1], Some(Value::String(ref string)) => assert_eq!(string, "not json"));
+        assert_matches!(data[2], Some(Value::String(ref string)) => assert_eq!(string, "{}"));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_schema_before_fetch() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let statement = db.prepare("SELECT ? AS foo, 42 AS bar").expect("prepare");
+        let mut result_set = db
+            .execute_with_parameters::<(String, i32), _>(statement, |q| q.bind(&"baz"))
+            .expect("failed to execute");
+
+        assert_eq!(result_set.rows_fetched(), 0);
+        let names: Vec<&str> = result_set.schema().iter().map(|column_type| column_type.name.as_str()).collect();
+        assert_eq!(names, ["foo", "bar"]);
+        assert_eq!(result_set.schema()[1].datum_type, DatumType::Integer);
+
+        let data = result_set.next().expect("row").expect("fetch data");
+        assert_eq!(data, ("baz".to_owned(), 42));
+        assert!(result_set.next().is_none());
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_bind_null() {
+        let mut connection = connect_sql_server();
+
+        let data: (Option<i32>, Option<String>, i32) = connection
+            .handle()
+            .query_with_parameters("SELECT ?, ?, ?", |q| {
+                q.bind_null(odbc::ffi::SqlDataType::SQL_INTEGER)?
+                    .bind_null(odbc::ffi::SqlDataType::SQL_VARCHAR)?
+                    .bind(&42)
+            })
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(data, (None, None, 42));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_max_rows() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            max_rows: Some(2),
+            ..Default::default()
+        });
+
+        let data = connection
+            .handle()
+            .query::<i32>("SELECT 1 UNION SELECT 2 UNION SELECT 3")
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_eq!(data, [1, 2]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_take_rows() {
+        let mut connection = connect_sql_server();
+        let mut handle = connection.handle();
+
+        let data = handle
+            .query::<i32>("SELECT 1 UNION SELECT 2 UNION SELECT 3")
+            .expect("failed to run query")
+            .take_rows(2)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_eq!(data, [1, 2]);
+
+        let value: i32 = handle.query("SELECT 42").expect("failed to run query").single().expect("fetch data");
+        assert_eq!(value, 42);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_reset_session() {
+        let mut connection = connect_sql_server();
+
+        let catalog = connection.current_catalog().expect("current catalog");
+        connection.set_current_catalog("tempdb").expect("set catalog");
+        connection.set_read_only(true).expect("set read only");
+
+        assert!(connection.reset_session());
+        assert!(!connection.is_read_only().expect("is read only"));
+        if connection.connection_info().database.is_some() {
+            assert_eq!(connection.current_catalog().expect("current catalog"), catalog);
+        }
+
+        let value: i32 = connection.handle().query("SELECT 42").expect("failed to run query").single().expect("fetch data");
+        assert_eq!(value, 42);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_stop_on_row_error() {
+        let mut connection = connect_sql_server();
+
+        let rows = connection
+            .handle()
+            .query::<i32>("SELECT v FROM (VALUES (1), (NULL), (3)) AS t(v)")
+            .expect("failed to run query")
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows.len(), 3);
+        assert_matches!(rows[0], Ok(1));
+        assert_matches!(rows[1], Err(DataAccessError::FromRowError(_)));
+        assert_matches!(rows[2], Ok(3));
+
+        let mut connection = connect_sql_server_with_settings(Settings {
+            stop_on_row_error: true,
+            ..Default::default()
+        });
+
+        let rows = connection
+            .handle()
+            .query::<i32>("SELECT v FROM (VALUES (1), (NULL), (3)) AS t(v)")
+            .expect("failed to run query")
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows.len(), 2);
+        assert_matches!(rows[0], Ok(1));
+        assert_matches!(rows[1], Err(DataAccessError::FromRowError(_)));
+    }
+
+    #[cfg(all(feature = "test-sql-server", feature = "serde_json"))]
+    #[test]
+    fn test_sql_server_bind_json_value() {
+        use serde_json::json;
+
+        let mut connection = connect_sql_server();
+
+        let data: ValueRow = connection
+            .handle()
+            .query_with_parameters("SELECT ?, ?, ?, ?, ?, ?", |q| {
+                q.bind_value(&json!(42))?
+                    .bind_value(&json!(1.5))?
+                    .bind_value(&json!("foo"))?
+                    .bind_value(&json!(true))?
+                    .bind_value(&json!(null))?
+                    .bind_value(&json!([1, 2]))
+            })
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_matches!(data[0], Some(Value::Bigint(42)));
+        assert_matches!(data[1], Some(Value::Double(number)) => assert_eq!(number, 1.5));
+        assert_matches!(data[2], Some(Value::String(ref string)) => assert_eq!(string, "foo"));
+        assert_matches!(data[3], Some(Value::Bit(true)));
+        assert_matches!(data[4], None);
+        assert_matches!(data[5], Some(Value::String(ref string)) => assert_eq!(string, "[1,2]"));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_connect_with_prompt() {
+        let (mut connection, completed) = Odbc::connect_with_prompt(
+            sql_server_connection_string().as_str(),
+            DriverCompletion::Complete,
+            std::ptr::null_mut(),
+            Default::default(),
+        )
+        .expect("connect to SQL Server");
+
+        assert!(!completed.is_empty());
+        assert!(ConnectionInfo::from_connection_string(&completed).driver.is_some() || ConnectionInfo::from_connection_string(&completed).dsn.is_some());
+
+        let value: i32 = connection.handle().query("SELECT 42").expect("failed to run query").single().expect("fetch data");
+        assert_eq!(value, 42);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_resolved_connection_string() {
+        let connection = connect_sql_server();
+        assert!(connection.resolved_connection_string().is_none());
+
+        let connection = connect_sql_server_with_settings(Settings {
+            resolve_connection_string: true,
+            ..Default::default()
+        });
+
+        let resolved = connection.resolved_connection_string().expect("resolved connection string");
+        assert!(!resolved.is_empty());
+        if connection.connection_info().password {
+            assert!(resolved.contains("=***;"));
+        }
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_odbc_query_macro() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let rows = odbc_query!(db, "SELECT 1, 'foo' UNION SELECT 2, 'bar' ORDER BY 1", (i64, String)).expect("failed to run query");
+        assert_eq!(rows, [(1, "foo".to_owned()), (2, "bar".to_owned())]);
+
+        let (id, name) = (42, "baz".to_owned());
+        let rows = odbc_query!(db, "SELECT ?, ?", (i32, String), id, name).expect("failed to run query");
+        assert_eq!(rows, [(42, "baz".to_owned())]);
+
+        assert_matches!(odbc_query!(db, "SELECT 'foo'", i32), Err(QueryError::DataAccessError(_)));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_varchar_max() {
+        let mut connection = connect_sql_server();
+
+        let result_set = connection
+            .handle()
+            .query::<String>("SELECT REPLICATE(CAST('x' AS VARCHAR(MAX)), 3000000) AS val")
+            .expect("failed to run query");
+
+        assert_eq!(result_set.column_info()[0].column_size, None);
+
+        let value = result_set.single().expect("fetch data");
+        assert_eq!(value.len(), 3_000_000);
+        assert!(value.bytes().all(|b| b == b'x'));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_homogeneous_rows() {
+        let mut connection = connect_sql_server();
+
+        let rows = connection
+            .handle()
+            .query::<Vec<Option<f64>>>("SELECT CAST(1.5 AS FLOAT), CAST(NULL AS FLOAT), CAST(3 AS FLOAT)")
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_eq!(rows, [vec![Some(1.5), None, Some(3.0)]]);
+
+        let rows = connection
+            .handle()
+            .query::<Vec<f64>>("SELECT CAST(1.5 AS FLOAT), CAST(NULL AS FLOAT)")
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>();
+
+        assert_matches!(rows, Err(DataAccessError::FromRowError(_)));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_column_sources() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        db.query::<()>("CREATE TABLE #column_sources (id INT IDENTITY(1, 1), name VARCHAR(10))")
+            .expect("failed to run query")
+            .no_result()
+            .expect("no result");
+
+        let result_set = db
+            .query::<ValueRow>("SELECT id AS foo, name, 42 AS bar FROM #column_sources")
+            .expect("failed to run query");
+
+        let sources = result_set.column_sources();
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources[0].column.as_deref(), Some("id"));
+        assert_eq!(sources[1].column.as_deref(), Some("name"));
+        assert!(sources[0].table.as_deref().unwrap_or_default().starts_with("#column_sources"));
+        assert_eq!(sources[0].auto_increment, Some(true));
+        assert_eq!(sources[1].auto_increment, Some(false));
+        assert_eq!(sources[2].table, None);
+        assert_eq!(sources[2].column, None);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_query_with_named_map() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let mut parameters = std::collections::HashMap::new();
+        parameters.insert("id".to_owned(), Value::Integer(42));
+        parameters.insert("name".to_owned(), Value::String("foo".to_owned()));
+
+        let data: (i32, String, i32) = db
+            .query_with_named_map("SELECT :id, :name, :id + 1", &parameters)
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(data, (42, "foo".to_owned(), 43));
+
+        assert_matches!(
+            db.query_with_named_map::<ValueRow>("SELECT :id, :other", &parameters),
+            Err(QueryError::NamedParametersError(NamedParametersError::MissingParameters(ref names))) => assert_eq!(names, &["other".to_owned()])
+        );
+        assert_matches!(
+            db.query_with_named_map::<ValueRow>("SELECT :id", &parameters),
+            Err(QueryError::NamedParametersError(NamedParametersError::UnusedParameters(ref names))) => assert_eq!(names, &["name".to_owned()])
+        );
+    }
+
+    #[cfg(all(feature = "test-sql-server", feature = "encoding_rs"))]
+    #[test]
+    fn test_sql_server_narrow_encoding() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            narrow_encoding: Some(encoding_rs::WINDOWS_1252),
+            ..Default::default()
+        });
+
+        let value: String = connection
+            .handle()
+            .query("SELECT CAST('caf' + CHAR(233) AS VARCHAR(10) COLLATE SQL_Latin1_General_CP1_CI_AS)")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(value, "café");
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_end_transaction() {
+        let mut connection = connect_sql_server();
+
+        connection
+            .handle()
+            .query::<()>("CREATE TABLE #end_transaction (id INT)")
+            .expect("failed to run query")
+            .no_result()
+            .expect("no result");
+
+        connection.set_autocommit(false).expect("manual-commit mode");
+
+        connection
+            .handle()
+            .query::<()>("INSERT INTO #end_transaction VALUES (1)")
+            .expect("failed to run query")
+            .no_result()
+            .expect("no result");
+        connection.rollback().expect("rollback");
+
+        let count: i32 = connection
+            .handle()
+            .query("SELECT COUNT(*) FROM #end_transaction")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(count, 0);
+
+        connection
+            .handle()
+            .query::<()>("INSERT INTO #end_transaction VALUES (2)")
+            .expect("failed to run query")
+            .no_result()
+            .expect("no result");
+        connection.commit().expect("commit");
+        connection.rollback().expect("rollback");
+
+        let count: i32 = connection
+            .handle()
+            .query("SELECT COUNT(*) FROM #end_transaction")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(count, 1);
+
+        connection.set_autocommit(true).expect("auto-commit mode");
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_numbers_as_strings() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            numbers_as_strings: true,
+            ..Default::default()
+        });
+
+        let data: ValueRow = connection
+            .handle()
+            .query("SELECT CAST(9223372036854775807 AS BIGINT), CAST(12.3400 AS DECIMAL(10, 4)), CAST(1 AS BIT)")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_matches!(data[0], Some(Value::String(ref string)) => assert_eq!(string, "9223372036854775807"));
+        assert_matches!(data[1], Some(Value::String(ref string)) => assert_eq!(string, "12.3400"));
+        assert_matches!(data[2], Some(Value::Bit(true)));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_call_procedure() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        db.query::<()>("CREATE PROCEDURE #odbc_iter_call_procedure @a INTEGER, @doubled INTEGER OUTPUT AS BEGIN SET NOCOUNT ON; SELECT @a AS a; SELECT 'foo' AS b, 'bar' AS c; SET @doubled = @a * 2; END")
+            .expect("failed to create procedure")
+            .no_result()
+            .unwrap();
+
+        let result = db
+            .call_procedure("#odbc_iter_call_procedure", &[Some(Value::Integer(21))], &[odbc::ffi::SqlDataType::SQL_INTEGER])
+            .expect("failed to call procedure");
+
+        assert_eq!(result.result_sets.len(), 2);
+        assert_eq!(result.result_sets[0].0.len(), 1);
+        assert_eq!(result.result_sets[0].1, vec![vec![Some(Value::Integer(21))]]);
+        assert_eq!(result.result_sets[1].0.len(), 2);
+        assert_eq!(result.result_sets[1].1, vec![vec![Some(Value::String("foo".to_owned())), Some(Value::String("bar".to_owned()))]]);
+        assert_eq!(result.output_parameters, vec![Some(Value::Integer(42))]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_call_procedure_no_result_set() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        db.query::<()>("CREATE PROCEDURE #odbc_iter_call_procedure_outputs @a BIGINT, @doubled BIGINT OUTPUT, @at DATETIME2 OUTPUT, @name VARCHAR(10) OUTPUT, @nothing INTEGER OUTPUT AS BEGIN CREATE TABLE #t (x INTEGER); INSERT INTO #t VALUES (1); SET @doubled = @a * 2; SET @at = '2020-01-02 03:04:05'; SET @name = 'foo'; SET @nothing = NULL; END")
+            .expect("failed to create procedure")
+            .no_result()
+            .unwrap();
+
+        let result = db
+            .call_procedure(
+                "#odbc_iter_call_procedure_outputs",
+                &[Some(Value::Bigint(21))],
+                &[
+                    odbc::ffi::SqlDataType::SQL_EXT_BIGINT,
+                    odbc::ffi::SqlDataType::SQL_TIMESTAMP,
+                    odbc::ffi::SqlDataType::SQL_VARCHAR,
+                    odbc::ffi::SqlDataType::SQL_INTEGER,
+                ],
+            )
+            .expect("failed to call procedure");
+
+        assert!(result.result_sets.is_empty());
+        assert_eq!(result.output_parameters[0], Some(Value::Bigint(42)));
+        assert_matches!(&result.output_parameters[1], Some(Value::Timestamp(SqlTimestamp { year: 2020, month: 1, day: 2, hour: 3, minute: 4, second: 5, .. })));
+        assert_eq!(result.output_parameters[2], Some(Value::String("foo".to_owned())));
+        assert_eq!(result.output_parameters[3], None);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_call_procedure_output_truncated() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            output_parameter_max_length: Some(2),
+            ..Default::default()
+        });
+        let mut db = connection.handle();
+
+        db.query::<()>("CREATE PROCEDURE #odbc_iter_call_procedure_long @name VARCHAR(10) OUTPUT AS BEGIN SET @name = 'foobar'; END")
+            .expect("failed to create procedure")
+            .no_result()
+            .unwrap();
+
+        assert_matches!(
+            db.call_procedure("#odbc_iter_call_procedure_long", &[], &[odbc::ffi::SqlDataType::SQL_VARCHAR]),
+            Err(QueryError::OutputParameterTruncated { parameter: 0, max_length: 2 })
+        );
+        assert_matches!(
+            db.call_procedure("foo; DROP TABLE bar", &[], &[]),
+            Err(QueryError::InvalidProcedureName(_))
+        );
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_wrap_result() {
+        let settings = Settings::default();
+        let environment = odbc::create_environment_v3().expect("failed to create environment");
+        let connection = environment
+            .connect_with_connection_string(&sql_server_connection_string())
+            .expect("failed to connect");
+        let result = odbc::Statement::with_parent(&connection)
+            .expect("failed to allocate statement")
+            .exec_direct("SELECT 42 AS foo")
+            .expect("failed to execute");
+
+        let rows = ResultSet::<ValueRow, _, _>::wrap(result, &settings, DefaultConfiguration)
+            .expect("failed to wrap result")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to fetch rows");
+
+        assert_eq!(rows, vec![vec![Some(Value::Integer(42))]]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_free_statements() {
+        let mut connection = connect_sql_server();
+
+        {
+            let mut db = connection.handle();
+            let result_set = db.query::<ValueRow>("SELECT 1 UNION ALL SELECT 2").expect("failed to run query");
+            std::mem::forget(result_set);
+        }
+        assert_eq!(connection.free_statements(), 1);
+        assert_eq!(connection.free_statements(), 0);
+
+        {
+            let mut db = connection.handle();
+            db.query::<ValueRow>("SELECT 1").expect("failed to run query").for_each(drop);
+        }
+        assert_eq!(connection.free_statements(), 0);
+
+        let mut db = connection.handle();
+        let value = db.query::<i32>("SELECT 42").expect("failed to run query").single().expect("fetch data");
+        assert_eq!(value, 42);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_odbc_escape_sequences() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let value = db
+            // SQL Server provides date literal as DATETIME
+            .query::<(SqlTimestamp, String)>("SELECT {d '2020-01-02'}, {fn UCASE('foo')}")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!((value.0.year, value.0.month, value.0.day), (2020, 1, 2));
+        assert_eq!(value.1, "FOO");
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_last_insert_id() {
+        let mut connection = connect_sql_server();
+        assert!(connection.dbms_name().expect("failed to get DBMS name").contains("SQL Server"));
+
+        let mut db = connection.handle();
+        db.query::<()>("CREATE TABLE #odbc_iter_last_insert_id (id BIGINT IDENTITY(40, 2), val INT)")
+            .expect("failed to create table")
+            .no_result()
+            .unwrap();
+
+        db.query::<()>("INSERT INTO #odbc_iter_last_insert_id (val) VALUES (1)").expect("failed to insert").no_result().unwrap();
+        assert_eq!(db.last_insert_id().expect("failed to get last insert ID"), Some(40));
+
+        db.query::<()>("INSERT INTO #odbc_iter_last_insert_id (val) VALUES (2)").expect("failed to insert").no_result().unwrap();
+        assert_eq!(db.last_insert_id().expect("failed to get last insert ID"), Some(42));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_prepared_column_names() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let statement = db.prepare("SELECT 1 AS foo, 'x' AS bar WHERE 1 = ?").expect("prepare statement");
+        assert_eq!(statement.column_names().expect("failed to get column names"), ["foo", "bar"]);
+
+        let statement = db.prepare_described("SELECT 1 AS foo, 'x' AS bar").expect("prepare statement");
+        assert_eq!(statement.column_names().expect("failed to get column names"), ["foo", "bar"]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_time_fraction() {
+        let mut connection = connect_sql_server();
+
+        let time = connection
+            .handle()
+            .query::<SqlSsTime2>("SELECT CAST('12:17:40.1234567' AS TIME(7))")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(time.fraction, 123_456_700);
+
+        let mut connection = connect_sql_server_with_settings(Settings {
+            fraction_unit: FractionUnit::HundredNanoseconds,
+            ..Default::default()
+        });
+
+        let time = connection
+            .handle()
+            .query::<SqlSsTime2>("SELECT CAST('12:17:40.0000123' AS TIME(7))")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        // SQL Server provides nanoseconds (12300) which are now scaled as 100ns units
+        assert_eq!(time.fraction, 1_230_000);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_snapshot_isolation() {
+        let mut connection = connect_sql_server();
+
+        assert!(connection.set_isolation_level(IsolationLevel::Snapshot).expect("set isolation level"));
+
+        // 5 is SNAPSHOT
+        let level = connection
+            .handle()
+            .query::<i16>("SELECT transaction_isolation_level FROM sys.dm_exec_sessions WHERE session_id = @@SPID")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(level, 5);
+
+        assert!(connection.set_isolation_level(IsolationLevel::ReadCommitted).expect("set isolation level"));
+
+        let level = connection
+            .handle()
+            .query::<i16>("SELECT transaction_isolation_level FROM sys.dm_exec_sessions WHERE session_id = @@SPID")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(level, 2);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_select_columns() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let result_set = db
+            .query::<ValueRow>("SELECT 1 AS a, 'foo' AS b, 3 AS c, 'bar' AS d")
+            .expect("failed to run query")
+            .select_columns(&[0, 3])
+            .expect("select columns");
+
+        assert_eq!(result_set.schema().iter().map(|column_type| column_type.name.as_str()).collect::<Vec<_>>(), ["a", "d"]);
+        let data = result_set.collect::<Result<Vec<_>, _>>().expect("fetch data");
+        assert_eq!(data, [vec![Some(Value::Integer(1)), Some(Value::String("bar".to_owned()))]]);
+
+        assert_matches!(
+            db.query::<ValueRow>("SELECT 1 AS a").expect("failed to run query").select_columns(&[1]),
+            Err(ResultSetError::NoSuchColumn(1))
+        );
+        assert_matches!(
+            db.query::<ValueRow>("SELECT 1 AS a, 2 AS b").expect("failed to run query").select_columns(&[1, 0]),
+            Err(ResultSetError::UnorderedColumns(ref indices)) => assert_eq!(indices, &[1, 0])
+        );
+        assert_matches!(
+            db.query::<ValueRow>("SELECT 1 AS a, 2 AS b").expect("failed to run query").select_columns(&[0, 0]),
+            Err(ResultSetError::UnorderedColumns(_))
+        );
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_by_name() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        for query in &[
+            "SELECT 42 AS id, 'foo' AS name",
+            "SELECT 'foo' AS name, 42 AS id",
+            "SELECT 'foo' AS name, 'bar' AS extra, 42 AS ID",
+        ] {
+            let data = db
+                .query::<ValueRow>(query)
+                .expect("failed to run query")
+                .by_name::<(i32, String)>(&["id", "name"])
+                .expect("by name")
+                .collect::<Result<Vec<_>, _>>()
+                .expect("fetch data");
+
+            assert_eq!(data, [(42, "foo".to_owned())]);
+        }
+
+        assert_matches!(
+            db.query::<ValueRow>("SELECT 42 AS id").expect("failed to run query").by_name::<(i32, String)>(&["id", "name"]),
+            Err(ResultSetError::NoSuchColumnName(ref name)) => assert_eq!(name, "name")
+        );
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_sql_server_query_with_json_params() {
+        use serde_json::json;
+
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        let data = db
+            .query_with_json_params::<(i64, String, Option<String>)>("SELECT ?, ?, ? WHERE '?' = '?'", &[json!(42), json!("foo"), json!(null)])
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(data, (42, "foo".to_owned(), None));
+
+        assert_matches!(
+            db.query_with_json_params::<ValueRow>("SELECT ?, ?", &[json!(1)]),
+            Err(QueryError::ParameterCountMismatch { placeholders: 2, parameters: 1 })
+        );
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_bind_wide() {
+        let mut connection = connect_sql_server();
+        let mut db = connection.handle();
+
+        db.query::<()>("CREATE TABLE #odbc_iter_wide (val NVARCHAR(20))")
+            .expect("failed to create table")
+            .no_result()
+            .unwrap();
+
+        let value = String::from("Fóó 日本 ż");
+        db.query_with_parameters::<(), _>("INSERT INTO #odbc_iter_wide VALUES (?)", |q| q.bind_wide(&value))
+            .expect("failed to insert")
+            .no_result()
+            .unwrap();
+        drop(value);
+
+        let data = db
+            .query::<String>("SELECT val FROM #odbc_iter_wide")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(data, "Fóó 日本 ż");
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_strict_column_count() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            strict_column_count: true,
+            ..Default::default()
+        });
+        let mut db = connection.handle();
+
+        let data = db
+            .query::<(i32, String)>("SELECT 42, 'foo'")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data, (42, "foo".to_owned()));
+
+        assert_matches!(
+            db.query::<(i32, String)>("SELECT 42, 'foo', 'bar'"),
+            Err(QueryError::ResultSetError(ResultSetError::UnexpectedNumberOfColumns { expected: 2, got: 3 }))
+        );
+
+        // row types of unknown number of columns are not checked
+        let data = db
+            .query::<ValueRow>("SELECT 42, 'foo', 'bar'")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+        assert_eq!(data.len(), 3);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_current_row_number() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            cursor_type: CursorType::Static,
+            ..Default::default()
+        });
+        let mut db = connection.handle();
+
+        let mut result_set = db
+            .query::<i32>("SELECT * FROM (VALUES (1), (2), (3)) AS t (id)")
+            .expect("failed to run query");
+
+        assert_eq!(result_set.next().unwrap().expect("fetch row"), 1);
+        assert_eq!(result_set.current_row_number().expect("row number"), Some(1));
+        assert_eq!(result_set.next().unwrap().expect("fetch row"), 2);
+        assert_eq!(result_set.current_row_number().expect("row number"), Some(2));
+        drop(result_set);
+
+        let mut connection = connect_sql_server();
+        let mut result_set = connection
+            .handle()
+            .query::<i32>("SELECT 1")
+            .expect("failed to run query");
+
+        assert_eq!(result_set.next().unwrap().expect("fetch row"), 1);
+        assert_eq!(result_set.current_row_number().expect("row number"), None);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_guid() {
+        let mut connection = connect_sql_server();
+
+        let guid = connection
+            .handle()
+            .query::<Guid>("SELECT CAST('6F9619FF-8B86-D011-B42D-00C04FC964FF' AS UNIQUEIDENTIFIER)")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(
+            guid.as_bytes(),
+            &[0xff, 0x19, 0x96, 0x6f, 0x86, 0x8b, 0x11, 0xd0, 0xb4, 0x2d, 0x00, 0xc0, 0x4f, 0xc9, 0x64, 0xff]
+        );
+        assert_eq!(guid.to_canonical_string(), "6f9619ff-8b86-d011-b42d-00c04fc964ff");
+
+        let mut connection = connect_sql_server_with_settings(Settings {
+            guid_bytes: true,
+            ..Default::default()
+        });
+
+        let value = connection
+            .handle()
+            .query::<Value>("SELECT CAST('6F9619FF-8B86-D011-B42D-00C04FC964FF' AS UNIQUEIDENTIFIER)")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(value.as_str(), Some("6f9619ff-8b86-d011-b42d-00c04fc964ff"));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_attribute_error_diagnostics() {
+        let mut connection = connect_sql_server();
+
+        let err = connection.set_current_catalog("odbc_iter_no_such_database").unwrap_err();
+        let diagnostic = err.source().expect("diagnostic record");
+        assert!(diagnostic.to_string().contains("odbc_iter_no_such_database"));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_read_only() {
+        let mut connection = connect_sql_server();
+
+        assert!(!connection.is_read_only().expect("access mode"));
+        connection.set_read_only(true).expect("set access mode");
+        assert!(connection.is_read_only().expect("access mode"));
+        connection.set_read_only(false).expect("set access mode");
+        assert!(!connection.is_read_only().expect("access mode"));
+    }
+
+    #[cfg(feature = "test-hive")]
+    #[test]
+    fn test_hive_empty_data_set() {
+        let mut hive = connect_hive();
+
+        let data = hive
+            .handle()
+            .query::<ValueRow>("USE default;")
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert!(data.is_empty());
+    }
+
+    #[cfg(feature = "test-hive")]
+    #[test]
+    fn test_hive_long_string_fetch_utf_8() {
+        let mut hive = connect_hive();
+
+        let data = hive
+            .handle()
+            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+    }
+
+    #[cfg(feature = "test-monetdb")]
+    #[test]
+    fn test_moentdb_long_string_fetch_utf_8() {
+        let mut monetdb = crate::tests::connect_monetdb();
+
+        let data = monetdb
+            .handle()
+            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_long_string_fetch_utf_16_bind() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            utf_16_strings: true,
+            ..Default::default()
+        });
+
+        let utf_16_string = LONG_STRING.encode_utf16().collect::<Vec<u16>>();
+
+        let mut handle = connection.handle();
+
+        let statement = handle
+            .prepare("SELECT ? AS val;")
+            .expect("prepare statement");
+
+        let data: Vec<ValueRow> = handle
+            .execute_with_parameters(statement, |q| q.bind(&utf_16_string))
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_long_string_fetch_utf_16_bind_string_utf_16() {
+        let mut connection = connect_sql_server_with_settings(Settings {
+            utf_16_strings: true,
+            ..Default::default()
+        });
+
+        let utf_16_string = StringUtf16::from(LONG_STRING);
+
+        let mut handle = connection.handle();
+
+        let statement = handle
+            .prepare("SELECT ? AS val;")
+            .expect("prepare statement");
+
+        let data: Vec<ValueRow> = handle
+            .execute_with_parameters(statement, |q| q.bind(&utf_16_string))
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+    }
+
+    #[cfg(feature = "test-hive")]
+    #[test]
+    fn test_hive_long_string_fetch_utf_16() {
+        let mut hive = connect_hive_with_settings(Settings {
+            utf_16_strings: true,
+            ..Default::default()
+        });
+
+        let data = hive
+            .handle()
+            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+    }
+
+    #[cfg(feature = "test-monetdb")]
+    #[test]
+    fn test_moentdb_long_string_fetch_utf_16() {
+        let mut monetdb = connect_monetdb_with_settings(Settings {
+            utf_16_strings: true,
+            ..Default::default()
+        });
+
+        let data = monetdb
+            .handle()
+            .query::<ValueRow>(&format!("SELECT '{}'", LONG_STRING))
+            .expect("failed to run query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("fetch data");
+
+        assert_matches!(data[0][0], Some(Value::String(ref string)) => assert_eq!(string, LONG_STRING));
+    }
+
+    #[test]
+    fn test_split_queries() {
+        let queries = split_queries(
+            r#"-- Foo
+---
+CREATE DATABASE IF NOT EXISTS daily_reports;
+USE daily_reports;
+
+SELECT *;"#,
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .expect("failed to parse");
+        assert_eq!(
+            queries,
+            [
+                "CREATE DATABASE IF NOT EXISTS daily_reports;",
+                "USE daily_reports;",
+                "SELECT *;"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_queries_end_white() {
+        let queries = split_queries(
+            r#"USE daily_reports;
+SELECT *;
+
+"#,
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .expect("failed to parse");
+        assert_eq!(queries, ["USE daily_reports;", "SELECT *;"]);
+    }
+
+    #[test]
+    fn test_split_queries_simple() {
+        let queries = split_queries("SELECT 42;\nSELECT 24;\nSELECT 'foo';")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(queries, ["SELECT 42;", "SELECT 24;", "SELECT 'foo';"]);
+    }
+
+    #[test]
+    fn test_split_queries_semicolon() {
+        let queries = split_queries("SELECT 'foo; bar';\nSELECT 1;")
             .collect::<Result<Vec<_>, _>>()
             .expect("failed to parse");
         assert_eq!(queries, [r#"SELECT 'foo; bar';"#, "SELECT 1;"]);
@@ -1197,6 +2920,31 @@ SELECT *;
         );
     }
 
+    #[test]
+    fn test_count_placeholders() {
+        assert_eq!(count_placeholders("SELECT 1"), 0);
+        assert_eq!(count_placeholders("SELECT ? AS a, ? AS b"), 2);
+        assert_eq!(count_placeholders("SELECT '?', \"?\" FROM foo WHERE bar = ?"), 1);
+        assert_eq!(count_placeholders("SELECT 'it''s?' WHERE a = ? -- and b = ?\nAND c = ?"), 2);
+        assert_eq!(count_placeholders("{? = call foo(?, '?')}"), 2);
+    }
+
+    #[test]
+    fn test_split_queries_odbc_escape_sequences() {
+        let queries = split_queries("SELECT {d '2020-01-01'} AS d, {t '12:30:00'} AS t;\nSELECT {ts '2020-01-01 12:30:00'};\nSELECT {fn UCASE('a;b')} FROM foo WHERE {fn LENGTH(bar)} > 1;\n{call foo(?, '}; x')}")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(
+            queries,
+            [
+                "SELECT {d '2020-01-01'} AS d, {t '12:30:00'} AS t;",
+                "SELECT {ts '2020-01-01 12:30:00'};",
+                "SELECT {fn UCASE('a;b')} FROM foo WHERE {fn LENGTH(bar)} > 1;",
+                "{call foo(?, '}; x')}",
+            ]
+        );
+    }
+
     #[test]
     fn test_split_queries_escaped_quote() {
         let queries = split_queries("SELECT 'foo; b\\'ar';\nSELECT 1;")
@@ -1276,16 +3024,98 @@ SELECT *;
         assert_eq!(queries, ["SELECT 1;", "SELECT 2;", "SELECT 3;"]);
     }
 
+    #[test]
+    fn test_split_queries_no_terminator() {
+        let queries = split_queries("SELECT 1;\nSELECT 2")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(queries, ["SELECT 1;", "SELECT 2"]);
+    }
+
+    #[test]
+    fn test_split_queries_no_terminator_white_comment() {
+        let queries = split_queries("SELECT 1;\n-- foo\nSELECT 'foo; bar' \r\n\n")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(queries, ["SELECT 1;", "SELECT 'foo; bar'"]);
+    }
+
+    #[test]
+    fn test_split_queries_empty() {
+        assert!(split_queries("").next().is_none());
+        assert!(split_queries("   \n  ").next().is_none());
+        assert!(split_queries(" \t\r\n \r\n").next().is_none());
+    }
+
+    #[test]
+    fn test_split_queries_only_comments() {
+        assert!(split_queries("-- just a comment\n").next().is_none());
+        assert!(split_queries("-- just a comment").next().is_none());
+        assert!(split_queries("\n-- foo\r\n!outputformat vertical\n  -- bar").next().is_none());
+
+        let queries = split_queries("SELECT 1;\n-- just a comment")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(queries, ["SELECT 1;"]);
+    }
+
+    #[test]
+    fn test_split_queries_crlf() {
+        let queries = split_queries("-- Foo\r\n---\r\nCREATE DATABASE IF NOT EXISTS daily_reports;\r\nUSE daily_reports;\r\n\r\nSELECT *;")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(
+            queries,
+            [
+                "CREATE DATABASE IF NOT EXISTS daily_reports;",
+                "USE daily_reports;",
+                "SELECT *;"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_queries_crlf_end_white() {
+        let queries = split_queries("USE daily_reports;\r\nSELECT *;\r\n\r\n")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(queries, ["USE daily_reports;", "SELECT *;"]);
+    }
+
+    #[test]
+    fn test_split_queries_crlf_simple() {
+        let queries = split_queries("SELECT 42;\r\nSELECT 24;\r\nSELECT 'foo';")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(queries, ["SELECT 42;", "SELECT 24;", "SELECT 'foo';"]);
+    }
+
+    #[test]
+    fn test_split_queries_crlf_white_comment() {
+        let queries = split_queries("SELECT 1; \t \r\nSELECT 2; -- foo bar\r\n \r\nSELECT 3; ")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(queries, ["SELECT 1;", "SELECT 2;", "SELECT 3;"]);
+    }
+
     #[cfg(feature = "test-sql-server")]
     #[test]
     fn test_sql_server_debug() {
         let mut connection = connect_sql_server_with_settings(Settings {
             utf_16_strings: true,
+            ..Default::default()
         });
 
+        // `Settings::narrow_encoding` is only part of the debug output with "encoding_rs" feature
+        #[cfg(feature = "encoding_rs")]
+        let narrow_encoding = ", narrow_encoding: None";
+        #[cfg(not(feature = "encoding_rs"))]
+        let narrow_encoding = "";
+        let settings = format!("Settings {{ utf_16_strings: true, utf_16_lossy: false, trim_char: false, read_as: [], numbers_as_strings: false, driver_default_types: [], fraction_unit: Nanoseconds, guid_bytes: false, log_parameters: false, redact_parameters: false, duplicate_column_names: Keep, read_only: false, cursor_type: ForwardOnly, concurrency: ReadOnly, stop_on_row_error: false, max_rows: None, max_length: None, output_parameter_max_length: None, resolve_connection_string: false, packet_size: None, slow_query_threshold: None, strict_nullability: false, strict_column_count: false, name_unnamed_columns: false, vendor_types: [], json_columns: [], json_type_names: false{} }}", narrow_encoding);
+
         assert_eq!(
             format!("{:?}", connection),
-            "Connection { settings: Settings { utf_16_strings: true } }"
+            format!("Connection {{ settings: {} }}", settings)
         );
 
         let utf_16_string = LONG_STRING.encode_utf16().collect::<Vec<u16>>();
@@ -1293,7 +3123,7 @@ SELECT *;
         let mut handle = connection.handle();
         assert_eq!(
             format!("{:?}", handle),
-            "Handle { connection: Connection { settings: Settings { utf_16_strings: true } }, configuration: DefaultConfiguration }"
+            format!("Handle {{ connection: Connection {{ settings: {} }}, configuration: DefaultConfiguration }}", settings)
         );
 
         let statement = handle
@@ -1310,6 +3140,6 @@ SELECT *;
             })
             .expect("failed to run query");
 
-        assert_eq!(format!("{:?}", result_set), "ResultSet { schema: [ColumnType { datum_type: String, odbc_type: SQL_EXT_WVARCHAR, nullable: true, name: \"foo\" }, ColumnType { datum_type: Integer, odbc_type: SQL_INTEGER, nullable: true, name: \"bar\" }, ColumnType { datum_type: Bit, odbc_type: SQL_EXT_BIT, nullable: true, name: \"baz\" }], columns: 3, settings: Settings { utf_16_strings: true }, configuration: DefaultConfiguration }");
+        assert_eq!(format!("{:?}", result_set), format!("ResultSet {{ schema: [ColumnType {{ datum_type: String, odbc_type: SQL_EXT_WVARCHAR, nullable: true, name: \"foo\" }}, ColumnType {{ datum_type: Integer, odbc_type: SQL_INTEGER, nullable: true, name: \"bar\" }}, ColumnType {{ datum_type: Bit, odbc_type: SQL_EXT_BIT, nullable: true, name: \"baz\" }}], columns: 3, settings: {}, configuration: DefaultConfiguration }}", settings));
     }
 }
@@ -1,5 +1,6 @@
 //! Extra types that represent SQL data values but with extra from/to implementations for `OdbcType` so they can be bound to query parameter
 
+use std::convert::TryFrom;
 use std::fmt;
 
 // Allow for custom type implementation
@@ -9,7 +10,7 @@ pub use odbc::{ffi, OdbcType};
 mod sql_timestamp {
     use super::*;
     use chrono::naive::{NaiveDate, NaiveDateTime};
-    use chrono::{Datelike, Timelike};
+    use chrono::{DateTime, Datelike, FixedOffset, Offset, TimeZone, Timelike};
     use odbc::SqlTimestamp;
 
     /// `SqlTimestamp` type that can be created from number of seconds since epoch as represented by `f64` value.
@@ -58,6 +59,16 @@ mod sql_timestamp {
         }
     }
 
+    /// Time zone aware date and time is bound as its UTC date and time.
+    ///
+    /// This is the representation for `DATETIME`/`DATETIME2`/`TIMESTAMP` columns that have no offset; see `OffsetTimestamp`
+    /// to store the offset in offset aware columns (e.g. SQL Server `DATETIMEOFFSET`).
+    impl<Tz: TimeZone> From<DateTime<Tz>> for UnixTimestamp {
+        fn from(value: DateTime<Tz>) -> UnixTimestamp {
+            value.naive_utc().into()
+        }
+    }
+
     unsafe impl<'a> OdbcType<'a> for UnixTimestamp {
         fn sql_data_type() -> ffi::SqlDataType {
             SqlTimestamp::sql_data_type()
@@ -78,6 +89,47 @@ mod sql_timestamp {
         }
     }
 
+    /// Time zone aware date and time bound as string with the offset (e.g. "2019-01-10 10:17:40.229 +02:00") so it can be
+    /// stored with the offset in offset aware columns like SQL Server `DATETIMEOFFSET`.
+    ///
+    /// Note that when string is converted to date and time column without offset (e.g. `DATETIME2`) databases usually
+    /// discard the offset keeping the local time; use `UnixTimestamp` to store such columns as UTC.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct OffsetTimestamp(String);
+
+    impl OffsetTimestamp {
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl<Tz: TimeZone> From<DateTime<Tz>> for OffsetTimestamp {
+        fn from(value: DateTime<Tz>) -> OffsetTimestamp {
+            let value: DateTime<FixedOffset> = value.with_timezone(&value.offset().fix());
+            OffsetTimestamp(value.format("%Y-%m-%d %H:%M:%S%.f %:z").to_string())
+        }
+    }
+
+    unsafe impl<'a> OdbcType<'a> for OffsetTimestamp {
+        fn sql_data_type() -> ffi::SqlDataType {
+            String::sql_data_type()
+        }
+        fn c_data_type() -> ffi::SqlCDataType {
+            String::c_data_type()
+        }
+
+        fn convert(buffer: &'a [u8]) -> Self {
+            OffsetTimestamp(String::convert(buffer))
+        }
+
+        fn column_size(&self) -> ffi::SQLULEN {
+            self.0.column_size()
+        }
+        fn value_ptr(&self) -> ffi::SQLPOINTER {
+            self.0.value_ptr()
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         pub use super::*;
@@ -95,6 +147,24 @@ mod sql_timestamp {
             assert_eq!(ts.0.fraction / 1000, 229123); // need to round it up as precision is not best
         }
 
+        #[test]
+        fn test_timestamp_from_date_time() {
+            use chrono::Utc;
+
+            let date_time = FixedOffset::east(2 * 3600).ymd(2019, 1, 10).and_hms_micro(12, 17, 40, 229123);
+            let ts: UnixTimestamp = date_time.into();
+            assert_eq!(ts.as_naive_date_time(), NaiveDate::from_ymd(2019, 1, 10).and_hms_micro(10, 17, 40, 229123));
+
+            let ts: UnixTimestamp = date_time.with_timezone(&Utc).into();
+            assert_eq!(ts.as_naive_date_time(), NaiveDate::from_ymd(2019, 1, 10).and_hms_micro(10, 17, 40, 229123));
+
+            let ts: OffsetTimestamp = date_time.into();
+            assert_eq!(ts.as_str(), "2019-01-10 12:17:40.229123 +02:00");
+
+            let ts: OffsetTimestamp = Utc.ymd(2019, 1, 10).and_hms(10, 17, 40).into();
+            assert_eq!(ts.as_str(), "2019-01-10 10:17:40 +00:00");
+        }
+
         #[test]
         fn test_timestamp_as_date_time() {
             let ts: UnixTimestamp = 1547115460.2291234.into();
@@ -194,6 +264,96 @@ unsafe impl<'a> OdbcType<'a> for StringUtf16 {
     }
 }
 
+/// String read with driver default C type conversion (`SQL_C_DEFAULT`) of the column; invalid UTF-8 sequences are replaced
+/// with U+FFFD.
+///
+/// This is only meaningful for columns the driver provides as character data by default (see `Settings::driver_default_types`).
+#[derive(PartialEq, Eq, Debug)]
+pub struct DriverDefaultString(pub String);
+
+unsafe impl<'a> OdbcType<'a> for DriverDefaultString {
+    fn sql_data_type() -> ffi::SqlDataType {
+        String::sql_data_type()
+    }
+    fn c_data_type() -> ffi::SqlCDataType {
+        ffi::SqlCDataType::SQL_C_DEFAULT
+    }
+
+    fn convert(buffer: &[u8]) -> Self {
+        DriverDefaultString(String::from_utf8_lossy(buffer).into_owned())
+    }
+
+    fn column_size(&self) -> ffi::SQLULEN {
+        self.0.column_size()
+    }
+
+    fn value_ptr(&self) -> ffi::SQLPOINTER {
+        self.0.value_ptr()
+    }
+}
+
+/// GUID value (`SQL_GUID`) read as raw 16 bytes (`SQL_C_BINARY`) exactly as provided by the driver.
+///
+/// Use `Column::into_guid()` or query `Guid` directly to get it; see also `Settings::guid_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Guid(pub [u8; 16]);
+
+impl Guid {
+    /// Create `Guid` from raw bytes in ODBC `SQLGUID` structure layout; `None` if there are not exactly 16 bytes.
+    pub fn from_slice(bytes: &[u8]) -> Option<Guid> {
+        <[u8; 16]>::try_from(bytes).ok().map(Guid)
+    }
+
+    /// Raw bytes in ODBC `SQLGUID` structure layout as provided by the driver (see `to_canonical_string()`).
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Canonical lowercase "xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx" representation.
+    ///
+    /// Bytes are interpreted according to ODBC `SQLGUID` structure layout with first three fields in little-endian byte
+    /// order as provided by e.g. SQL Server.
+    pub fn to_canonical_string(&self) -> String {
+        let b = &self.0;
+        format!(
+            "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+            b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]
+        )
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_canonical_string())
+    }
+}
+
+unsafe impl<'a> OdbcType<'a> for Guid {
+    fn sql_data_type() -> ffi::SqlDataType {
+        ffi::SqlDataType::SQL_EXT_GUID
+    }
+    fn c_data_type() -> ffi::SqlCDataType {
+        ffi::SqlCDataType::SQL_C_BINARY
+    }
+
+    // `Column::into_guid()` reads the data as `&[u8]` and fails on wrong length instead
+    fn convert(buffer: &[u8]) -> Self {
+        debug_assert_eq!(buffer.len(), 16, "GUID data is not 16 bytes long");
+        let mut bytes = [0u8; 16];
+        let len = buffer.len().min(bytes.len());
+        bytes[..len].copy_from_slice(&buffer[..len]);
+        Guid(bytes)
+    }
+
+    fn column_size(&self) -> ffi::SQLULEN {
+        self.0.len() as ffi::SQLULEN
+    }
+
+    fn value_ptr(&self) -> ffi::SQLPOINTER {
+        self.0.as_ptr() as ffi::SQLPOINTER
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for StringUtf16 {
     fn deserialize<D>(deserializer: D) -> std::result::Result<StringUtf16, D::Error>
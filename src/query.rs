@@ -1,20 +1,32 @@
 use error_context::prelude::*;
-use log::{debug, log_enabled, trace};
+use log::{debug, log_enabled, trace, warn};
 use odbc::{
-    Allocated, ColumnDescriptor, Connection as OdbcConnection, DiagnosticRecord, Executed,
-    NoResult, OdbcType, Prepared, ResultSetState, Statement,
+    ffi, Allocated, ColumnDescriptor, Connection as OdbcConnection, DataSource, DiagnosticRecord, Executed, GetDiagRec,
+    Handle as OdbcHandle, NoResult, OdbcType, Prepared, ResultSetState, SqlDate, SqlSsTime2, SqlTime, SqlTimestamp,
+    Statement,
 };
+use regex::Regex;
 use lazy_static::lazy_static;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
+use crate::attributes::{self, Accepted};
+use crate::connection_string::{redact_connection_string, validate_connection_string, ConnectionInfo};
 use crate::result_set::{DataAccessError, ResultSet, ResultSetError};
-use crate::row::{Settings, Configuration, DefaultConfiguration, ColumnType, UnsupportedSqlDataType, TryFromRow};
+use crate::row::{Settings, Concurrency, CursorType, Configuration, DefaultConfiguration, ColumnType, UnsupportedSqlDataType, TryFromRow};
+use crate::value::Value;
+use crate::odbc_type::StringUtf16;
+use crate::value_row::ValueRow;
+use crate::query_builder::{NamedParametersError, QueryBuilder};
 use crate::{Odbc, OdbcError};
 use crate::stats::{self, ConnectionOpenGuard};
+use crate::spans;
 
 /// Errors related to execution of queries.
 ///
@@ -23,16 +35,26 @@ use crate::stats::{self, ConnectionOpenGuard};
 #[allow(clippy::large_enum_variant)]
 pub enum QueryError {
     OdbcError(OdbcError),
+    /// Execution of one-off query failed; contains description of the query (see `QueryError::query()`).
+    ExecutionError(OdbcError, String),
     BindError(DiagnosticRecord),
     UnsupportedSqlDataType(UnsupportedSqlDataType),
     ResultSetError(ResultSetError),
     DataAccessError(DataAccessError),
+    NamedParametersError(NamedParametersError),
+    ParameterCountMismatch { placeholders: usize, parameters: usize },
+    /// Procedure name passed to `Handle::call_procedure()` is not a valid (optionally qualified and quoted) identifier.
+    InvalidProcedureName(String),
+    /// Value of output parameter (0 based) of `Handle::call_procedure()` was longer than
+    /// `Settings::output_parameter_max_length`.
+    OutputParameterTruncated { parameter: usize, max_length: usize },
 }
 
 impl fmt::Display for QueryError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             QueryError::OdbcError(err) => write!(f, "{}", err),
+            QueryError::ExecutionError(err, query) => write!(f, "{}: {}", err, query),
             QueryError::BindError(_) => {
                 write!(f, "ODBC call failed while binding parameter to statement")
             }
@@ -41,6 +63,14 @@ impl fmt::Display for QueryError {
             }
             QueryError::ResultSetError(_) => write!(f, "failed to create result set for query"),
             QueryError::DataAccessError(_) => write!(f, "failed to access result data"),
+            QueryError::NamedParametersError(_) => write!(f, "failed to bind named query parameters"),
+            QueryError::ParameterCountMismatch { placeholders, parameters } => {
+                write!(f, "query has {} parameter placeholders but {} parameters were provided", placeholders, parameters)
+            }
+            QueryError::InvalidProcedureName(name) => write!(f, "invalid procedure name: {:?}", name),
+            QueryError::OutputParameterTruncated { parameter, max_length } => {
+                write!(f, "value of output parameter {} is longer than {} bytes", parameter, max_length)
+            }
         }
     }
 }
@@ -49,10 +79,50 @@ impl Error for QueryError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             QueryError::OdbcError(err) => err.source(),
+            QueryError::ExecutionError(err, _) => err.source(),
             QueryError::BindError(err) => Some(err),
             QueryError::UnsupportedSqlDataType(err) => Some(err),
             QueryError::ResultSetError(err) => Some(err),
             QueryError::DataAccessError(err) => Some(err),
+            QueryError::NamedParametersError(err) => Some(err),
+            QueryError::ParameterCountMismatch { .. } => None,
+            QueryError::InvalidProcedureName(_) => None,
+            QueryError::OutputParameterTruncated { .. } => None,
+        }
+    }
+}
+
+// queries longer than that are truncated in error messages
+const QUERY_DESCRIPTION_MAX_LEN: usize = 256;
+
+impl QueryError {
+    fn execution_error(err: OdbcError, query: &str) -> QueryError {
+        QueryError::ExecutionError(err, describe_query(query))
+    }
+
+    /// Description of the failed one-off query if this is `QueryError::ExecutionError`.
+    ///
+    /// Queries longer than 256 characters are truncated and followed by hash of the full query text so that different
+    /// long queries can still be told apart in logs.
+    pub fn query(&self) -> Option<&str> {
+        match self {
+            QueryError::ExecutionError(_, query) => Some(query),
+            _ => None,
+        }
+    }
+}
+
+fn describe_query(query: &str) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let query = query.trim();
+    match query.char_indices().nth(QUERY_DESCRIPTION_MAX_LEN) {
+        None => query.to_owned(),
+        Some((end, _)) => {
+            let mut hasher = DefaultHasher::new();
+            query.hash(&mut hasher);
+            format!("{}... [query hash: {:016x}]", &query[..end], hasher.finish())
         }
     }
 }
@@ -93,6 +163,12 @@ impl From<DataAccessError> for QueryError {
     }
 }
 
+impl From<NamedParametersError> for QueryError {
+    fn from(err: NamedParametersError) -> QueryError {
+        QueryError::NamedParametersError(err)
+    }
+}
+
 /// Error that can happen when binding values to parametrized queries.
 #[derive(Debug)]
 pub struct BindError(DiagnosticRecord);
@@ -115,10 +191,25 @@ impl From<DiagnosticRecord> for BindError {
     }
 }
 
+// special `SQLTables` arguments for listing catalogs, schemas and table types with all other arguments empty
+const SQL_ALL_CATALOGS: &str = "%";
+const SQL_ALL_SCHEMAS: &str = "%";
+const SQL_ALL_TABLE_TYPES: &str = "%";
+
+// `SQLTables` result columns: TABLE_CAT, TABLE_SCHEM, TABLE_NAME, TABLE_TYPE, REMARKS
+const TABLES_CATALOG_COLUMN: usize = 0;
+const TABLES_SCHEMA_COLUMN: usize = 1;
+const TABLES_TABLE_TYPE_COLUMN: usize = 3;
+
 /// Controls binding of parametrized query values.
 pub struct Binder<'h, 't, S> {
     statement: Statement<'h, 't, S, NoResult>,
     index: u16,
+    // rendered values are recorded only if they are going to be logged (see `log_parameters`)
+    record_parameters: bool,
+    parameters: Vec<String>,
+    // values bound with `bind_owned` that need to live until parameters are reset after execution
+    owned: Vec<Box<dyn Any>>,
 }
 
 impl<S> fmt::Debug for Binder<'_, '_, S> {
@@ -130,6 +221,8 @@ impl<S> fmt::Debug for Binder<'_, '_, S> {
 }
 
 impl<'h, 't, S> Binder<'h, 't, S> {
+    /// Bind value to next parameter placeholder.
+    /// Any `OdbcType` can be bound; e.g. `bool` is bound as `BIT` (`SQL_C_BIT`).
     pub fn bind<'new_t, T>(self, value: &'new_t T) -> Result<Binder<'h, 'new_t, S>, BindError>
     where
         T: OdbcType<'new_t> + Debug,
@@ -139,13 +232,431 @@ impl<'h, 't, S> Binder<'h, 't, S> {
         if log_enabled!(::log::Level::Trace) {
             trace!("Parameter {}: {:?}", index, value);
         }
+        let record_parameters = self.record_parameters;
+        let mut parameters = self.parameters;
+        if record_parameters {
+            parameters.push(format!("{:?}", value));
+        }
+        let owned = self.owned;
         let statement = self.statement.bind_parameter(index, value)?;
 
-        Ok(Binder { statement, index })
+        Ok(Binder { statement, index, record_parameters, parameters, owned })
+    }
+
+    /// Bind owned value to next parameter placeholder keeping it alive until the statement is executed.
+    fn bind_owned<T>(self, value: T) -> Result<Binder<'h, 't, S>, BindError>
+    where
+        T: for<'a> OdbcType<'a> + Debug + 'static,
+    {
+        let value = Box::new(value);
+        // boxed value does not move and it is dropped only after parameters were reset following execution (see `into_parts`)
+        let value_ref: &'t T = unsafe { &*(value.as_ref() as *const T) };
+        let mut binder = self.bind(value_ref)?;
+        binder.owned.push(value);
+        Ok(binder)
     }
 
-    fn into_inner(self) -> Statement<'h, 't, S, NoResult> {
-        self.statement
+    /// Bind string to next parameter placeholder encoded as UTF-16 (`SQL_C_WCHAR`) wide string.
+    ///
+    /// Use this with drivers that corrupt non-ASCII characters of narrow string parameters (e.g. due to code page
+    /// conversion). The encoded copy of the string is kept by the binder so the string does not need to outlive it;
+    /// see `StringUtf16` for binding by reference.
+    pub fn bind_wide(self, value: &str) -> Result<Binder<'h, 't, S>, BindError> {
+        self.bind_owned(StringUtf16::from(value))
+    }
+
+    /// Bind JSON value to next parameter placeholder (with "serde_json" feature).
+    ///
+    /// Value is bound according to its kind: integer as `BIGINT` (or numeric string if it does not fit `i64`), other numbers as `DOUBLE`,
+    /// string as `VARCHAR`, boolean as `BIT` and `null` as `NULL` of `SQL_VARCHAR` type.
+    /// Arrays and objects are bound as their JSON text.
+    #[cfg(feature = "serde_json")]
+    pub fn bind_value(self, value: &serde_json::Value) -> Result<Binder<'h, 't, S>, BindError> {
+        use serde_json::Value as Json;
+
+        match value {
+            Json::Null => self.bind_null(ffi::SqlDataType::SQL_VARCHAR),
+            Json::Bool(value) => self.bind_owned(*value),
+            Json::Number(number) => {
+                if let Some(number) = number.as_i64() {
+                    self.bind_owned(number)
+                } else if number.is_u64() {
+                    self.bind_owned(number.to_string())
+                } else {
+                    self.bind_owned(number.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            Json::String(value) => self.bind_owned(value.clone()),
+            Json::Array(_) | Json::Object(_) => self.bind_owned(value.to_string()),
+        }
+    }
+
+    /// Bind copy of the value (or NULL of `SQL_VARCHAR` type for `None`) to next parameter placeholder.
+    fn bind_owned_value(self, value: Option<&Value>) -> Result<Binder<'h, 't, S>, BindError> {
+        match value {
+            None => self.bind_null(ffi::SqlDataType::SQL_VARCHAR),
+            Some(Value::Bit(value)) => self.bind_owned(*value),
+            Some(Value::Tinyint(value)) => self.bind_owned(*value),
+            Some(Value::Smallint(value)) => self.bind_owned(*value),
+            Some(Value::Integer(value)) => self.bind_owned(*value),
+            Some(Value::Bigint(value)) => self.bind_owned(*value),
+            Some(Value::Float(value)) => self.bind_owned(*value),
+            Some(Value::Double(value)) => self.bind_owned(*value),
+            #[cfg(feature = "rust_decimal")]
+            Some(Value::Decimal(value)) => self.bind_owned(value.to_string()),
+            Some(Value::String(value)) => self.bind_owned(value.clone()),
+            Some(Value::Timestamp(value)) => self.bind_owned(value.clone()),
+            Some(Value::Date(value)) => self.bind_owned(value.clone()),
+            Some(Value::Time(value)) => self.bind_owned(value.clone()),
+            #[cfg(feature = "serde_json")]
+            Some(Value::Json(value)) => self.bind_owned(value.to_string()),
+        }
+    }
+
+    /// Bind NULL value of given SQL type to next parameter placeholder.
+    ///
+    /// Some drivers reject NULL parameters bound without SQL type matching the column (e.g. `ffi::SqlDataType::SQL_INTEGER`).
+    pub fn bind_null(self, sql_type: ffi::SqlDataType) -> Result<Binder<'h, 't, S>, BindError> {
+        // indicator is read by the driver at execution time (and may be written to) so it is kept with owned values
+        let mut indicator = Box::new(ffi::SQL_NULL_DATA);
+
+        let index = self.index + 1;
+        if log_enabled!(::log::Level::Trace) {
+            trace!("Parameter {}: NULL ({:?})", index, sql_type);
+        }
+        let mut parameters = self.parameters;
+        if self.record_parameters {
+            parameters.push("NULL".to_owned());
+        }
+
+        match unsafe {
+            ffi::SQLBindParameter(
+                self.statement.handle(),
+                index,
+                ffi::SQL_PARAM_INPUT,
+                ffi::SQL_C_CHAR,
+                sql_type,
+                1,
+                0,
+                std::ptr::null_mut(),
+                0,
+                indicator.as_mut() as *mut ffi::SQLLEN,
+            )
+        } {
+            ffi::SQL_SUCCESS | ffi::SQL_SUCCESS_WITH_INFO => (),
+            _ => return Err(BindError(self.statement.get_diag_rec(1).unwrap_or_else(DiagnosticRecord::empty))),
+        }
+
+        let mut owned = self.owned;
+        owned.push(indicator);
+        Ok(Binder { statement: self.statement, index, record_parameters: self.record_parameters, parameters, owned })
+    }
+
+    /// Bind all values of a slice to following parameter placeholders in order.
+    pub fn bind_all<'new_t, T>(self, values: &'new_t [T]) -> Result<Binder<'h, 'new_t, S>, BindError>
+    where
+        T: OdbcType<'new_t> + Debug,
+        't: 'new_t,
+    {
+        let binder: Binder<'h, 'new_t, S> = self;
+        values.iter().try_fold(binder, |binder, value| binder.bind(value))
+    }
+
+    /// Bind all values of a tuple of references (e.g. `(&42, &"foo", &1.5)`) in order.
+    pub fn bind_params<'new_t, P>(self, params: P) -> Result<Binder<'h, 'new_t, S>, BindError>
+    where
+        P: BindParams<'new_t>,
+        't: 'new_t,
+    {
+        params.bind_params(self)
+    }
+
+    /// Owned values need to be kept alive until bound parameters are reset after execution.
+    fn into_parts(self) -> (Statement<'h, 't, S, NoResult>, BoundParameters, Vec<Box<dyn Any>>) {
+        let parameters = BoundParameters { count: self.index, values: self.parameters };
+        (self.statement, parameters, self.owned)
+    }
+}
+
+/// Tuples of references to values that can be bound to parametrized query in order with `Binder::bind_params()`.
+pub trait BindParams<'t> {
+    fn bind_params<'h, 'o, S>(self, binder: Binder<'h, 'o, S>) -> Result<Binder<'h, 't, S>, BindError>
+    where
+        'o: 't;
+}
+
+macro_rules! bind_params_tuple {
+    ($(
+        $Tuple:ident {
+            $(($idx:tt) -> $T:ident)+
+        }
+    )+) => {
+        $(
+            impl<'t, $($T: OdbcType<'t> + Debug),+> BindParams<'t> for ($(&'t $T,)+) {
+                fn bind_params<'h, 'o, S>(self, binder: Binder<'h, 'o, S>) -> Result<Binder<'h, 't, S>, BindError>
+                where
+                    'o: 't,
+                {
+                    $(let binder = binder.bind(self.$idx)?;)+
+                    Ok(binder)
+                }
+            }
+        )+
+    }
+}
+
+bind_params_tuple! {
+    Tuple1 {
+        (0) -> TA
+    }
+    Tuple2 {
+        (0) -> TA
+        (1) -> TB
+    }
+    Tuple3 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+    }
+    Tuple4 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+    }
+    Tuple5 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+    }
+    Tuple6 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+        (5) -> TF
+    }
+    Tuple7 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+        (5) -> TF
+        (6) -> TG
+    }
+    Tuple8 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+        (5) -> TF
+        (6) -> TG
+        (7) -> TH
+    }
+    Tuple9 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+        (5) -> TF
+        (6) -> TG
+        (7) -> TH
+        (8) -> TI
+    }
+    Tuple10 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+        (5) -> TF
+        (6) -> TG
+        (7) -> TH
+        (8) -> TI
+        (9) -> TJ
+    }
+    Tuple11 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+        (5) -> TF
+        (6) -> TG
+        (7) -> TH
+        (8) -> TI
+        (9) -> TJ
+        (10) -> TK
+    }
+    Tuple12 {
+        (0) -> TA
+        (1) -> TB
+        (2) -> TC
+        (3) -> TD
+        (4) -> TE
+        (5) -> TF
+        (6) -> TG
+        (7) -> TH
+        (8) -> TI
+        (9) -> TJ
+        (10) -> TK
+        (11) -> TL
+    }
+}
+
+/// Parameter values owned by the crate and kept alive while the statement is executed with `Handle::execute_owned()`.
+///
+/// Values can be computed in the same expression as the call since they don't need to outlive the statement; they are
+/// bound in order as copies like with `Handle::call_procedure()`, with `None` bound as NULL of `SQL_VARCHAR` type.
+/// Can be created from tuple of values convertible to `Value` (e.g. `(42, "foo".to_owned(), 1.5)`).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OwnedParams {
+    values: Vec<Option<Value>>,
+}
+
+impl OwnedParams {
+    pub fn new() -> OwnedParams {
+        OwnedParams::default()
+    }
+
+    /// Add value for next parameter placeholder.
+    pub fn push(mut self, value: impl Into<Value>) -> OwnedParams {
+        self.values.push(Some(value.into()));
+        self
+    }
+
+    /// Add NULL for next parameter placeholder.
+    pub fn push_null(mut self) -> OwnedParams {
+        self.values.push(None);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn values(&self) -> &[Option<Value>] {
+        &self.values
+    }
+}
+
+impl From<Vec<Option<Value>>> for OwnedParams {
+    fn from(values: Vec<Option<Value>>) -> OwnedParams {
+        OwnedParams { values }
+    }
+}
+
+impl From<Vec<Value>> for OwnedParams {
+    fn from(values: Vec<Value>) -> OwnedParams {
+        OwnedParams { values: values.into_iter().map(Some).collect() }
+    }
+}
+
+macro_rules! owned_params_tuple {
+    ($(($($T:ident $idx:tt),+))+) => {
+        $(
+            impl<$($T: Into<Value>),+> From<($($T,)+)> for OwnedParams {
+                fn from(params: ($($T,)+)) -> OwnedParams {
+                    OwnedParams { values: vec![$(Some(params.$idx.into())),+] }
+                }
+            }
+        )+
+    }
+}
+
+owned_params_tuple! {
+    (TA 0)
+    (TA 0, TB 1)
+    (TA 0, TB 1, TC 2)
+    (TA 0, TB 1, TC 2, TD 3)
+    (TA 0, TB 1, TC 2, TD 3, TE 4)
+    (TA 0, TB 1, TC 2, TD 3, TE 4, TF 5)
+    (TA 0, TB 1, TC 2, TD 3, TE 4, TF 5, TG 6)
+    (TA 0, TB 1, TC 2, TD 3, TE 4, TF 5, TG 6, TH 7)
+    (TA 0, TB 1, TC 2, TD 3, TE 4, TF 5, TG 6, TH 7, TI 8)
+    (TA 0, TB 1, TC 2, TD 3, TE 4, TF 5, TG 6, TH 7, TI 8, TJ 9)
+    (TA 0, TB 1, TC 2, TD 3, TE 4, TF 5, TG 6, TH 7, TI 8, TJ 9, TK 10)
+    (TA 0, TB 1, TC 2, TD 3, TE 4, TF 5, TG 6, TH 7, TI 8, TJ 9, TK 10, TL 11)
+}
+
+/// Execute query with fixed shape result and collect all rows into `Vec` of given row type (e.g. tuple).
+///
+/// Expands to `Handle::query()` (or `Handle::query_with_parameters()` binding following parameters in order with
+/// `Binder::bind_params()`) and collects the rows; both query and row errors are returned as `QueryError`.
+/// Parameters are bound by reference so they need to be variables or constants.
+///
+/// Example
+/// ========
+/// ```rust
+/// use odbc_iter::{odbc_query, Odbc};
+///
+/// let connection_string = std::env::var("DB_CONNECTION_STRING")
+///     .expect("DB_CONNECTION_STRING environment not set");
+/// let mut connection = Odbc::connect(&connection_string)
+///     .expect("failed to connect to database");
+/// let mut db = connection.handle();
+///
+/// let rows = odbc_query!(db, "SELECT 1, 'foo'", (i64, String)).expect("failed to run query");
+/// assert_eq!(rows, [(1, "foo".to_owned())]);
+///
+/// let id = 42;
+/// let rows = odbc_query!(db, "SELECT ?", i32, id).expect("failed to run query");
+/// assert_eq!(rows, [42]);
+/// ```
+#[macro_export]
+macro_rules! odbc_query {
+    ($handle:expr, $query:expr, $row:ty $(,)?) => {
+        $handle
+            .query::<$row>($query)
+            .map_err($crate::QueryError::from)
+            .and_then(|result_set| result_set.collect::<Result<Vec<$row>, _>>().map_err($crate::QueryError::from))
+    };
+    ($handle:expr, $query:expr, $row:ty, $($param:expr),+ $(,)?) => {
+        $handle
+            .query_with_parameters::<$row, _>($query, |binder| binder.bind_params(($(&$param,)+)))
+            .map_err($crate::QueryError::from)
+            .and_then(|result_set| result_set.collect::<Result<Vec<$row>, _>>().map_err($crate::QueryError::from))
+    };
+}
+
+/// Number of parameters bound by `Binder` and their rendered values if they were recorded for logging.
+struct BoundParameters {
+    count: u16,
+    values: Vec<String>,
+}
+
+/// `true` if `Binder` needs to record rendered parameter values for `log_parameters()`.
+fn record_parameters(settings: &Settings) -> bool {
+    settings.log_parameters && !settings.redact_parameters && log_enabled!(::log::Level::Debug)
+}
+
+/// Log query with all bound parameters in one entry if enabled with `Settings::log_parameters`.
+fn log_parameters(settings: &Settings, query: &str, parameters: &BoundParameters) {
+    if settings.log_parameters && log_enabled!(::log::Level::Debug) {
+        if settings.redact_parameters {
+            debug!("Executing: {} with parameters [{}]", query, vec!["<redacted>"; parameters.count as usize].join(", "));
+        } else {
+            debug!("Executing: {} with parameters [{}]", query, parameters.values.join(", "));
+        }
+    }
+}
+
+/// Log warning if query execution took longer than `Settings::slow_query_threshold`.
+fn log_slow_query(settings: &Settings, query: &str, execution_time: Duration) {
+    if let Some(threshold) = settings.slow_query_threshold {
+        if execution_time > threshold {
+            warn!("Slow query took {:?} to execute: {}", execution_time, query);
+        }
     }
 }
 
@@ -154,19 +665,55 @@ impl<'h, 't, S> From<Statement<'h, 'h, S, NoResult>> for Binder<'h, 'h, S> {
         Binder {
             statement,
             index: 0,
+            record_parameters: false,
+            parameters: Vec::new(),
+            owned: Vec::new(),
         }
     }
 }
 
+impl<'h, S> Binder<'h, 'h, S> {
+    /// Binder recording parameter values only if they are going to be logged according to the settings.
+    fn with_settings(statement: Statement<'h, 'h, S, NoResult>, settings: &Settings) -> Binder<'h, 'h, S> {
+        let mut binder = Binder::from(statement);
+        binder.record_parameters = record_parameters(settings);
+        binder
+    }
+}
+
 /// ODBC prepared statement.
-pub struct PreparedStatement<'h>(Statement<'h, 'h, odbc::Prepared, odbc::NoResult>);
+pub struct PreparedStatement<'h> {
+    statement: Statement<'h, 'h, odbc::Prepared, odbc::NoResult>,
+    query: String,
+    described: Option<PreparedSchema>,
+}
+
+/// Description of parameter of prepared statement as provided by the ODBC driver (`SQLDescribeParam`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterType {
+    /// ODBC SQL Data Type of the parameter.
+    pub odbc_type: ffi::SqlDataType,
+    /// `true` if parameter can be `NULL` (or driver does not know).
+    pub nullable: bool,
+    /// Parameter size (e.g. maximum length of string or precision of numeric parameter) if provided by the ODBC driver.
+    pub parameter_size: Option<usize>,
+    /// Number of decimal digits (scale) of the parameter if provided by the ODBC driver.
+    pub decimal_digits: Option<u16>,
+}
+
+/// Parameter and result schemas cached by `Handle::prepare_described()`.
+#[derive(Debug, Clone)]
+pub(crate) struct PreparedSchema {
+    parameters: Vec<ParameterType>,
+    columns: Vec<ColumnType>,
+}
 
 impl<'h> fmt::Debug for PreparedStatement<'h> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut d = f.debug_struct("PreparedStatement");
 
-        let schema = (1..=self.0.num_result_cols().map_err(|_| std::fmt::Error)?)
-            .map(|i| self.0.describe_col(i as u16))
+        let schema = (1..=self.statement.num_result_cols().map_err(|_| std::fmt::Error)?)
+            .map(|i| self.statement.describe_col(i as u16))
             .collect::<Result<Vec<ColumnDescriptor>, _>>()
             .map_err(|_| std::fmt::Error)?;
 
@@ -178,15 +725,25 @@ impl<'h> fmt::Debug for PreparedStatement<'h> {
 impl<'h> PreparedStatement<'h> {
     pub(crate) fn from_statement(
         statement: Statement<'h, 'h, odbc::Prepared, odbc::NoResult>,
+        query: String,
+        described: Option<PreparedSchema>,
     ) -> PreparedStatement<'h> {
-        PreparedStatement(statement)
+        PreparedStatement { statement, query, described }
+    }
+
+    /// SQL text the statement was prepared with.
+    pub fn query(&self) -> &str {
+        &self.query
     }
 
     /// Query schema information deduced from prepared statement SQL text.
+    ///
+    /// Some drivers can't describe result columns before parameters are bound; in that case use `ResultSet::schema()` of the
+    /// result of `Handle::execute_with_parameters()` which is available before any row is fetched.
     pub fn schema(&self) -> Result<Vec<ColumnType>, QueryError> {
         (1..=self.columns()?)
             .map(|i| {
-                self.0
+                self.statement
                     .describe_col(i as u16)
                     .wrap_error_while("getting column description")
                     .map_err(QueryError::from)
@@ -195,25 +752,223 @@ impl<'h> PreparedStatement<'h> {
             .collect::<Result<_, _>>()
     }
 
+    /// Query only names of columns that would be returned by execution of this prepared statement.
+    ///
+    /// This is cheaper than `schema()` as column types are not described nor mapped; names cached by
+    /// `Handle::prepare_described()` are returned without querying the driver.
+    pub fn column_names(&self) -> Result<Vec<String>, OdbcError> {
+        if let Some(columns) = self.result_schema() {
+            return Ok(columns.iter().map(|column_type| column_type.name.clone()).collect())
+        }
+
+        (1..=self.columns()?)
+            .map(|i| attributes::get_column_attribute_string(&self.statement, i as u16, ffi::SQL_DESC_NAME, "getting column name"))
+            .collect()
+    }
+
     /// Query number of columns that would be returned by execution of this prepared statement.
     pub fn columns(&self) -> Result<i16, OdbcError> {
         Ok(self
-            .0
+            .statement
             .num_result_cols()
             .wrap_error_while("getting number of columns in prepared statement")?)
     }
+
+    /// Parameter schema described at prepare time if statement was prepared with `Handle::prepare_described()`.
+    ///
+    /// The description is kept when the statement is returned by `ResultSet::close()` so it is not queried again on repeated executions.
+    pub fn param_schema(&self) -> Option<&[ParameterType]> {
+        self.described.as_ref().map(|described| described.parameters.as_slice())
+    }
+
+    /// Result schema described at prepare time if statement was prepared with `Handle::prepare_described()`.
+    ///
+    /// See `schema()` for limitations of describing result columns before execution.
+    pub fn result_schema(&self) -> Option<&[ColumnType]> {
+        self.described.as_ref().map(|described| described.columns.as_slice())
+    }
+
+    fn describe_parameters(&self) -> Result<Vec<ParameterType>, OdbcError> {
+        let handle = unsafe { self.statement.handle() };
+
+        let mut count: ffi::SQLSMALLINT = 0;
+        attributes::check(
+            unsafe { ffi::SQLNumParams(handle, &mut count) },
+            ffi::SQL_HANDLE_STMT,
+            handle as ffi::SQLHANDLE,
+            "getting number of parameters",
+        )?;
+
+        (1..=count as u16)
+            .map(|parameter| {
+                let mut odbc_type = ffi::SQL_UNKNOWN_TYPE;
+                let mut parameter_size: ffi::SQLULEN = 0;
+                let mut decimal_digits: ffi::SQLSMALLINT = 0;
+                let mut nullable: ffi::SQLSMALLINT = 0;
+                attributes::check(unsafe {
+                    ffi::SQLDescribeParam(handle, parameter, &mut odbc_type, &mut parameter_size, &mut decimal_digits, &mut nullable)
+                }, ffi::SQL_HANDLE_STMT, handle as ffi::SQLHANDLE, "describing parameter")?;
+
+                Ok(ParameterType {
+                    odbc_type,
+                    nullable: nullable != ffi::SQL_NO_NULLS as ffi::SQLSMALLINT,
+                    parameter_size: if parameter_size == 0 { None } else { Some(parameter_size as usize) },
+                    // 0 is a valid scale (e.g. INTEGER or DECIMAL(10, 0))
+                    decimal_digits: u16::try_from(decimal_digits).ok(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Result sets and output parameter values of stored procedure called with `Handle::call_procedure()`.
+#[derive(Debug)]
+pub struct ProcedureResult {
+    /// Schema and rows of each result set with columns in order they were returned by the procedure.
+    pub result_sets: Vec<(Vec<ColumnType>, Vec<ValueRow>)>,
+    /// Values of output parameters in order they were requested as `Value` variant matching requested SQL type.
+    pub output_parameters: ValueRow,
+}
+
+// default maximum length in bytes of character output parameter value read by `Handle::call_procedure()`
+const OUTPUT_PARAMETER_MAX_LEN: usize = 4000;
+
+/// Buffer of output parameter of `Handle::call_procedure()` bound with C type matching requested SQL type.
+///
+/// Types without matching `Value` variant (e.g. `SQL_DECIMAL` or `SQL_VARCHAR`) are read as character data.
+struct OutputParameter {
+    sql_type: ffi::SqlDataType,
+    c_type: ffi::SqlCDataType,
+    column_size: ffi::SQLULEN,
+    decimal_digits: ffi::SQLSMALLINT,
+    buffer: Box<[u8]>,
+    indicator: Box<ffi::SQLLEN>,
+}
+
+impl OutputParameter {
+    fn new(sql_type: ffi::SqlDataType, max_length: usize) -> OutputParameter {
+        use ffi::SqlDataType::*;
+
+        fn fixed<'a, T: OdbcType<'a>>() -> (ffi::SqlCDataType, usize) {
+            (T::c_data_type(), std::mem::size_of::<T>())
+        }
+
+        let ((c_type, buffer_length), column_size, decimal_digits): (_, usize, ffi::SQLSMALLINT) = match sql_type {
+            SQL_EXT_BIT => (fixed::<bool>(), 1, 0),
+            SQL_EXT_TINYINT => (fixed::<i8>(), 3, 0),
+            SQL_SMALLINT => (fixed::<i16>(), 5, 0),
+            SQL_INTEGER => (fixed::<i32>(), 10, 0),
+            SQL_EXT_BIGINT => (fixed::<i64>(), 19, 0),
+            SQL_REAL | SQL_FLOAT => (fixed::<f32>(), 24, 0),
+            SQL_DOUBLE => (fixed::<f64>(), 53, 0),
+            SQL_TIMESTAMP => (fixed::<SqlTimestamp>(), 27, 7),
+            SQL_DATE => (fixed::<SqlDate>(), 10, 0),
+            SQL_TIME => (fixed::<SqlTime>(), 8, 0),
+            // extra byte for null terminator
+            _ => ((ffi::SqlCDataType::SQL_C_CHAR, max_length + 1), max_length, 0),
+        };
+
+        OutputParameter {
+            sql_type,
+            c_type,
+            column_size: column_size as ffi::SQLULEN,
+            decimal_digits,
+            buffer: vec![0u8; buffer_length].into_boxed_slice(),
+            indicator: Box::new(ffi::SQL_NULL_DATA),
+        }
+    }
+
+    /// Value written by the driver; `parameter` is index of the output parameter for error reporting.
+    fn value(&self, parameter: usize) -> Result<Option<Value>, QueryError> {
+        use ffi::SqlDataType::*;
+
+        let length = *self.indicator;
+        if length == ffi::SQL_NULL_DATA {
+            return Ok(None);
+        }
+
+        let buffer = &self.buffer[..];
+        Ok(Some(match self.sql_type {
+            SQL_EXT_BIT => Value::Bit(bool::convert(buffer)),
+            SQL_EXT_TINYINT => Value::Tinyint(i8::convert(buffer)),
+            SQL_SMALLINT => Value::Smallint(i16::convert(buffer)),
+            SQL_INTEGER => Value::Integer(i32::convert(buffer)),
+            SQL_EXT_BIGINT => Value::Bigint(i64::convert(buffer)),
+            SQL_REAL | SQL_FLOAT => Value::Float(f32::convert(buffer)),
+            SQL_DOUBLE => Value::Double(f64::convert(buffer)),
+            SQL_TIMESTAMP => Value::Timestamp(SqlTimestamp::convert(buffer)),
+            SQL_DATE => Value::Date(SqlDate::convert(buffer)),
+            SQL_TIME => {
+                let time = SqlTime::convert(buffer);
+                Value::Time(SqlSsTime2 {
+                    hour: time.hour,
+                    minute: time.minute,
+                    second: time.second,
+                    fraction: 0,
+                })
+            }
+            _ => {
+                let max_length = buffer.len() - 1;
+                // negative length other than NULL is `SQL_NO_TOTAL` (length unknown)
+                if length < 0 || length as usize > max_length {
+                    return Err(QueryError::OutputParameterTruncated { parameter, max_length });
+                }
+                Value::String(String::from_utf8_lossy(&buffer[..length as usize]).into_owned())
+            }
+        }))
+    }
+}
+
+/// `true` if procedure name is made of identifiers (plain, `[bracket]` or `"double quote"` quoted) separated with "."
+/// so it can be safely embedded in `{call ...}` escape sequence.
+fn valid_procedure_name(name: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(
+            r#"^(?:[\p{L}_@#][\p{L}\p{N}_@#$]*|\[(?:[^\]]|\]\])+\]|"(?:[^"]|"")+")(?:\.(?:[\p{L}_@#][\p{L}\p{N}_@#$]*|\[(?:[^\]]|\]\])+\]|"(?:[^"]|"")+"))*$"#
+        ).unwrap();
+    }
+    RE.is_match(name)
 }
 
 /// Database connection.
 pub struct Connection {
     connection: OdbcConnection<'static>,
     settings: Settings,
+    info: ConnectionInfo,
+    resolved_connection_string: Option<String>,
+    open_statements: OpenStatements,
     _stats_guard: ConnectionOpenGuard,
 }
 
 /// Assuming drivers support sending Connection between threads.
 unsafe impl Send for Connection {}
 
+/// Raw handles of statements of result sets that were not dropped yet (see `Connection::free_statements()`).
+#[derive(Debug, Default)]
+pub(crate) struct OpenStatements(Mutex<HashSet<usize>>);
+
+impl OpenStatements {
+    /// Returns key to unregister the statement with.
+    pub(crate) fn register(&self, statement: ffi::SQLHSTMT) -> usize {
+        let key = statement as usize;
+        self.0.lock().expect("open statements lock is poisoned").insert(key);
+        key
+    }
+
+    pub(crate) fn unregister(&self, key: usize) {
+        self.0.lock().expect("open statements lock is poisoned").remove(&key);
+    }
+
+    fn take(&self) -> Vec<ffi::SQLHSTMT> {
+        self.0
+            .lock()
+            .expect("open statements lock is poisoned")
+            .drain()
+            .map(|key| key as ffi::SQLHSTMT)
+            .collect()
+    }
+}
+
 lazy_static! {
     static ref CONNECT_MUTEX: Mutex<()> = Mutex::new(());
 }
@@ -261,20 +1016,377 @@ impl Connection {
         connection_string: &str,
         settings: Settings,
     ) -> Result<Connection, OdbcError> {
-        odbc.environment
+        validate_connection_string(connection_string).map_err(|problem| OdbcError(None, problem))?;
+
+        let info = ConnectionInfo::from_connection_string(connection_string);
+        let _span = spans::connect(&info);
+
+        let resolved_connection_string = if settings.resolve_connection_string {
+            let resolved = complete_connection_string(odbc, connection_string, ffi::SQL_DRIVER_NOPROMPT, std::ptr::null_mut())?;
+            Some(redact_connection_string(&resolved))
+        } else {
+            None
+        };
+
+        let data_source = DataSource::with_parent(&odbc.environment)
+            .wrap_error_while("allocating connection handle")?;
+
+        if let Some(packet_size) = settings.packet_size {
+            // must be set before connecting
+            if attributes::set_connection_attribute_integer(
+                &data_source,
+                ffi::SQL_ATTR_PACKET_SIZE,
+                packet_size,
+                "setting packet size",
+            )? != Accepted::Exact {
+                debug!("Driver did not set packet size exactly as requested: {}", packet_size);
+            }
+        }
+
+        data_source
             .connect_with_connection_string(connection_string)
             .wrap_error_while("connecting to database")
             .map_err(Into::into)
-            .map(|connection| {
-                Connection {
+            .and_then(|connection| {
+                let mut connection = Connection {
                     connection,
                     settings,
+                    info,
+                    resolved_connection_string,
+                    open_statements: OpenStatements::default(),
                     _stats_guard: ConnectionOpenGuard::new(),
+                };
+
+                if connection.settings.read_only && !connection.set_read_only(true)? {
+                    debug!("Driver did not set read-only access mode exactly as requested");
                 }
+
+                Ok(connection)
             })
     }
+
+    /// Connect to database letting the driver complete missing connection string attributes (`SQLDriverConnect`) and
+    /// return the connection together with completed connection string that can be stored for next time.
+    ///
+    /// With `DriverCompletion::Prompt` or when attributes are missing the driver may show a dialog owned by given window
+    /// (e.g. `HWND` on Windows); without a window (`std::ptr::null_mut()`) the driver can't prompt and fails instead.
+    /// The driver is connected once to complete the connection string and then again with the completed string using
+    /// given settings. This implementation will synchronize driver connect calls.
+    pub fn connect_with_prompt(
+        odbc: &'static Odbc,
+        connection_string: &str,
+        completion: DriverCompletion,
+        window_handle: ffi::SQLHWND,
+        settings: Settings,
+    ) -> Result<(Connection, String), OdbcError> {
+        let _guard = CONNECT_MUTEX.lock().expect("Connection Mutex is poisoned!");
+
+        let completion = match completion {
+            DriverCompletion::Complete => ffi::SQL_DRIVER_COMPLETE,
+            DriverCompletion::CompleteRequired => ffi::SQL_DRIVER_COMPLETE_REQUIRED,
+            DriverCompletion::Prompt => ffi::SQL_DRIVER_PROMPT,
+        };
+        let completed = complete_connection_string(odbc, connection_string, completion, window_handle)?;
+        let mut connection = unsafe { Self::with_settings_concurrent(odbc, &completed, Settings {
+            resolve_connection_string: false,
+            ..settings
+        })? };
+        connection.resolved_connection_string = Some(redact_connection_string(&completed));
+
+        Ok((connection, completed))
+    }
+
+    /// Disconnect from database reporting any error (e.g. due to transaction still in progress).
+    /// Dropping `Connection` also disconnects but errors are ignored.
+    pub fn disconnect(self) -> Result<(), OdbcError> {
+        debug!("Disconnecting from {}", self.info);
+        self.connection
+            .disconnect()
+            .wrap_error_while("disconnecting from database")?;
+        Ok(())
+    }
+
+    /// Summary of the connection string (driver, server, database, user) safe for logging as it never contains the password.
+    pub fn connection_info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+
+    /// Full connection string as completed by the driver (with defaults filled in) and with password replaced by `***`.
+    ///
+    /// Available if connected with `Settings::resolve_connection_string` or with `connect_with_prompt()`.
+    pub fn resolved_connection_string(&self) -> Option<&str> {
+        self.resolved_connection_string.as_deref()
+    }
+
+    /// Name of the database product the connection is connected to as reported by the driver (`SQL_DBMS_NAME`), e.g.
+    /// "Microsoft SQL Server".
+    pub fn dbms_name(&self) -> Result<String, OdbcError> {
+        attributes::get_info_string(&self.connection, ffi::SQL_DBMS_NAME, "getting DBMS name")
+    }
+
+    /// Switch connection between read-only and read-write access mode (`SQL_ATTR_ACCESS_MODE`).
+    ///
+    /// Returns `false` if driver reported that it substituted the requested mode.
+    /// Note that drivers may treat read-only mode as a hint only and still allow updates.
+    pub fn set_read_only(&mut self, read_only: bool) -> Result<bool, OdbcError> {
+        let mode = if read_only { SQL_MODE_READ_ONLY } else { SQL_MODE_READ_WRITE };
+        attributes::set_connection_attribute_integer(
+            &self.connection,
+            ffi::SQL_ATTR_ACCESS_MODE,
+            mode,
+            "setting connection access mode",
+        ).map(|accepted| accepted == Accepted::Exact)
+    }
+
+    /// Check if connection is in read-only access mode (`SQL_ATTR_ACCESS_MODE`).
+    pub fn is_read_only(&self) -> Result<bool, OdbcError> {
+        attributes::get_connection_attribute_integer(
+            &self.connection,
+            ffi::SQL_ATTR_ACCESS_MODE,
+            "getting connection access mode",
+        ).map(|mode| mode == SQL_MODE_READ_ONLY)
+    }
+
+    /// Set transaction isolation level of the connection (`SQL_ATTR_TXN_ISOLATION`) used by following transactions.
+    ///
+    /// This can't be changed while transaction is open.
+    /// Returns `false` if driver reported that it substituted the requested level.
+    ///
+    /// With SQL Server `IsolationLevel::Snapshot` lets reporting queries read row versions as of the start of the
+    /// transaction without taking shared locks so they neither block nor get blocked by writers (without resorting to
+    /// `WITH (NOLOCK)` dirty reads). The database needs `ALLOW_SNAPSHOT_ISOLATION` enabled for queries to succeed.
+    /// Note that `READ_COMMITTED_SNAPSHOT` is a database option that changes behaviour of `IsolationLevel::ReadCommitted`
+    /// and can't be set per session.
+    pub fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<bool, OdbcError> {
+        attributes::set_connection_attribute_integer(
+            &self.connection,
+            ffi::SQL_ATTR_TXN_ISOLATION,
+            level.attribute_value(),
+            "setting transaction isolation level",
+        ).map(|accepted| accepted == Accepted::Exact)
+    }
+
+    /// Set current catalog (database) of the connection (`SQL_ATTR_CURRENT_CATALOG`) so that unqualified names in
+    /// following queries resolve against it.
+    ///
+    /// Fails if the driver rejected the catalog (e.g. it does not exist or catalogs are not supported).
+    pub fn set_current_catalog(&mut self, catalog: &str) -> Result<(), OdbcError> {
+        attributes::set_connection_attribute_string(
+            &self.connection,
+            ffi::SQL_ATTR_CURRENT_CATALOG,
+            catalog,
+            "setting current catalog",
+        ).map(|_| ())
+    }
+
+    /// Get current catalog (database) of the connection (`SQL_ATTR_CURRENT_CATALOG`).
+    pub fn current_catalog(&self) -> Result<String, OdbcError> {
+        attributes::get_connection_attribute_string(
+            &self.connection,
+            ffi::SQL_ATTR_CURRENT_CATALOG,
+            "getting current catalog",
+        )
+    }
+
+    /// Switch connection between auto-commit and manual-commit mode (`SQL_ATTR_AUTOCOMMIT`).
+    ///
+    /// In manual-commit mode statements run in a transaction that is ended with `Connection::commit()` or
+    /// `Connection::rollback()`; switching back to auto-commit mode commits the open transaction.
+    pub fn set_autocommit(&mut self, autocommit: bool) -> Result<(), OdbcError> {
+        let mode = if autocommit { SQL_AUTOCOMMIT_ON } else { SQL_AUTOCOMMIT_OFF };
+        attributes::set_connection_attribute_integer(
+            &self.connection,
+            ffi::SQL_ATTR_AUTOCOMMIT,
+            mode,
+            "setting connection auto-commit mode",
+        ).map(|_| ())
+    }
+
+    /// Commit transaction of the connection with `SQLEndTran` (unlike `Handle::commit()` that runs "COMMIT" query).
+    ///
+    /// In auto-commit mode drivers commit each statement on execution so this makes sure prior statements are committed
+    /// also for drivers that defer commits.
+    pub fn commit(&mut self) -> Result<(), OdbcError> {
+        attributes::end_transaction(&self.connection, ffi::SQL_COMMIT, "committing transaction").map(|_| ())
+    }
+
+    /// Roll back transaction of the connection with `SQLEndTran` (unlike `Handle::rollback()` that runs "ROLLBACK" query).
+    pub fn rollback(&mut self) -> Result<(), OdbcError> {
+        attributes::end_transaction(&self.connection, ffi::SQL_ROLLBACK, "rolling back transaction").map(|_| ())
+    }
+
+    /// Cancel and free statements of result sets that were never dropped (e.g. leaked with `std::mem::forget()`) so they no
+    /// longer keep cursors open on the connection causing "invalid cursor state" or "connection is busy" errors.
+    ///
+    /// Result sets dropped normally (also when unwinding on panic) free their statements by themselves.
+    /// Returns number of statements freed.
+    pub fn free_statements(&mut self) -> usize {
+        let mut freed = 0;
+        for statement in self.open_statements.take() {
+            // exclusive borrow of the connection guarantees that result sets owning these statements will not be used any more
+            unsafe {
+                if ffi::SQLCancel(statement) == ffi::SQL_ERROR {
+                    debug!("Failed to cancel statement");
+                }
+                if ffi::SQLFreeStmt(statement, ffi::SQL_CLOSE) == ffi::SQL_ERROR {
+                    debug!("Failed to close cursor of statement");
+                }
+                match attributes::check(
+                    ffi::SQLFreeHandle(ffi::SQL_HANDLE_STMT, statement as ffi::SQLHANDLE),
+                    ffi::SQL_HANDLE_STMT,
+                    statement as ffi::SQLHANDLE,
+                    "freeing statement handle",
+                ) {
+                    Ok(_) => freed += 1,
+                    Err(err) => debug!("Failed to free statement: {}", err),
+                }
+            }
+        }
+        freed
+    }
+
+    /// Best-effort reset of session state before the connection is reused by other code (e.g. when returned to a pool).
+    ///
+    /// This rolls back any transaction open in manual commit mode, switches back to the database of the connection string
+    /// (if it specified one) and restores access mode requested by `Settings::read_only`.
+    /// Returns `false` if any of the steps failed in which case the connection should rather be discarded.
+    /// Note that transactions started explicitly with "START TRANSACTION" in auto-commit mode are not rolled back by the driver.
+    pub fn reset_session(&mut self) -> bool {
+        let mut ok = true;
+
+        if let Err(err) = attributes::end_transaction(&self.connection, ffi::SQL_ROLLBACK, "rolling back transaction") {
+            debug!("Failed to reset session of {}: {}", self.info, err);
+            ok = false;
+        }
+
+        if let Some(database) = self.info.database.clone() {
+            if let Err(err) = self.set_current_catalog(&database) {
+                debug!("Failed to reset session of {}: {}", self.info, err);
+                ok = false;
+            }
+        }
+
+        let read_only = self.settings.read_only;
+        match self.set_read_only(read_only) {
+            Ok(true) => (),
+            Ok(false) => {
+                debug!("Failed to reset session of {}: driver substituted access mode", self.info);
+                ok = false;
+            }
+            Err(err) => {
+                debug!("Failed to reset session of {}: {}", self.info, err);
+                ok = false;
+            }
+        }
+
+        ok
+    }
+}
+
+/// Transaction isolation level (`SQL_ATTR_TXN_ISOLATION`) set with `Connection::set_isolation_level()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IsolationLevel {
+    /// Transactions can read uncommitted changes of other transactions (`SQL_TXN_READ_UNCOMMITTED`).
+    ReadUncommitted,
+    /// Transactions read only committed changes (`SQL_TXN_READ_COMMITTED`).
+    ReadCommitted,
+    /// Rows read by transaction can't be changed by other transactions until it ends (`SQL_TXN_REPEATABLE_READ`).
+    RepeatableRead,
+    /// Transactions are fully isolated from each other (`SQL_TXN_SERIALIZABLE`).
+    Serializable,
+    /// Transaction reads consistent snapshot of data as of its start (SQL Server specific `SQL_TXN_SS_SNAPSHOT`).
+    Snapshot,
+}
+
+impl IsolationLevel {
+    fn attribute_value(self) -> ffi::SQLUINTEGER {
+        match self {
+            IsolationLevel::ReadUncommitted => SQL_TXN_READ_UNCOMMITTED,
+            IsolationLevel::ReadCommitted => SQL_TXN_READ_COMMITTED,
+            IsolationLevel::RepeatableRead => SQL_TXN_REPEATABLE_READ,
+            IsolationLevel::Serializable => SQL_TXN_SERIALIZABLE,
+            IsolationLevel::Snapshot => SQL_TXN_SS_SNAPSHOT,
+        }
+    }
+}
+
+/// How the driver should complete connection string in `Connection::connect_with_prompt()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriverCompletion {
+    /// Prompt only if connection string lacks attributes needed to connect (`SQL_DRIVER_COMPLETE`).
+    Complete,
+    /// Like `Complete` but only required attributes can be edited in the prompt (`SQL_DRIVER_COMPLETE_REQUIRED`).
+    CompleteRequired,
+    /// Always prompt with connection string attributes as initial values (`SQL_DRIVER_PROMPT`).
+    Prompt,
+}
+
+// based on ODBC recommendation of at least 1024 bytes; the output can't be fetched again without reconnecting
+const COMPLETED_CONNECTION_STRING_MAX_LEN: usize = 4096;
+
+/// Connect with driver completion on temporary connection handle to obtain completed (output) connection string.
+///
+/// `odbc` crate does not expose output connection string of `SQLDriverConnect` so it has to be obtained on separate handle.
+fn complete_connection_string(
+    odbc: &'static Odbc,
+    connection_string: &str,
+    completion: ffi::SqlDriverConnectOption,
+    window_handle: ffi::SQLHWND,
+) -> Result<String, OdbcError> {
+    let data_source = DataSource::with_parent(&odbc.environment)
+        .wrap_error_while("allocating connection handle")?;
+    let handle = unsafe { data_source.handle() };
+
+    let mut buffer = vec![0u8; COMPLETED_CONNECTION_STRING_MAX_LEN];
+    let mut length: ffi::SQLSMALLINT = 0;
+    let accepted = attributes::check(unsafe {
+        ffi::SQLDriverConnect(
+            handle,
+            window_handle,
+            connection_string.as_ptr(),
+            connection_string.len() as ffi::SQLSMALLINT,
+            buffer.as_mut_ptr(),
+            buffer.len() as ffi::SQLSMALLINT,
+            &mut length,
+            completion,
+        )
+    }, ffi::SQL_HANDLE_DBC, handle as ffi::SQLHANDLE, "connecting to database with driver completion")?;
+
+    let length = (length.max(0) as usize).min(buffer.len() - 1);
+    buffer.truncate(length);
+    if accepted == Accepted::WithInfo && length == COMPLETED_CONNECTION_STRING_MAX_LEN - 1 {
+        warn!("Completed connection string may have been truncated to {} bytes", length);
+    }
+
+    if unsafe { ffi::SQLDisconnect(handle) } == ffi::SQL_ERROR {
+        debug!("Failed to disconnect connection used to complete connection string");
+    }
+
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
 }
 
+const SQL_MODE_READ_WRITE: ffi::SQLUINTEGER = 0;
+const SQL_MODE_READ_ONLY: ffi::SQLUINTEGER = 1;
+const SQL_AUTOCOMMIT_OFF: ffi::SQLUINTEGER = 0;
+const SQL_AUTOCOMMIT_ON: ffi::SQLUINTEGER = 1;
+
+const SQL_TXN_READ_UNCOMMITTED: ffi::SQLUINTEGER = 1;
+const SQL_TXN_READ_COMMITTED: ffi::SQLUINTEGER = 2;
+const SQL_TXN_REPEATABLE_READ: ffi::SQLUINTEGER = 4;
+const SQL_TXN_SERIALIZABLE: ffi::SQLUINTEGER = 8;
+// msodbcsql.h
+const SQL_TXN_SS_SNAPSHOT: ffi::SQLUINTEGER = 0x20;
+
+const SQL_CURSOR_FORWARD_ONLY: ffi::SQLULEN = 0;
+const SQL_CURSOR_KEYSET_DRIVEN: ffi::SQLULEN = 1;
+const SQL_CURSOR_DYNAMIC: ffi::SQLULEN = 2;
+const SQL_CURSOR_STATIC: ffi::SQLULEN = 3;
+
+const SQL_CONCUR_READ_ONLY: ffi::SQLULEN = 1;
+const SQL_CONCUR_LOCK: ffi::SQLULEN = 2;
+const SQL_CONCUR_ROWVER: ffi::SQLULEN = 3;
+const SQL_CONCUR_VALUES: ffi::SQLULEN = 4;
+
 /// Statically ensures that `Connection` can only be used after `ResultSet` was consumed to avoid runtime
 /// errors.
 ///
@@ -311,10 +1423,52 @@ impl<'h, 'c: 'h, C: Configuration> Handle<'c, C> {
         }
     }
 
+    pub(crate) fn open_statements(&self) -> &'c OpenStatements {
+        &self.connection.open_statements
+    }
+
     fn statement(&'h self) -> Result<Statement<'c, 'c, Allocated, NoResult>, OdbcError> {
-        Statement::with_parent(&self.connection.connection)
-            .wrap_error_while("pairing statement with connection")
-            .map_err(Into::into)
+        let statement = Statement::with_parent(&self.connection.connection)
+            .wrap_error_while("pairing statement with connection")?;
+
+        let settings = &self.connection.settings;
+        if settings.cursor_type != CursorType::ForwardOnly {
+            let cursor_type = match settings.cursor_type {
+                CursorType::ForwardOnly => SQL_CURSOR_FORWARD_ONLY,
+                CursorType::Keyset => SQL_CURSOR_KEYSET_DRIVEN,
+                CursorType::Dynamic => SQL_CURSOR_DYNAMIC,
+                CursorType::Static => SQL_CURSOR_STATIC,
+            };
+            if attributes::set_statement_attribute_integer(&statement, ffi::SQL_ATTR_CURSOR_TYPE, cursor_type, "setting cursor type")? != Accepted::Exact {
+                debug!("Driver substituted requested cursor type {:?}", settings.cursor_type);
+            }
+        }
+
+        if settings.concurrency != Concurrency::ReadOnly {
+            let concurrency = match settings.concurrency {
+                Concurrency::ReadOnly => SQL_CONCUR_READ_ONLY,
+                Concurrency::Lock => SQL_CONCUR_LOCK,
+                Concurrency::RowVersion => SQL_CONCUR_ROWVER,
+                Concurrency::Values => SQL_CONCUR_VALUES,
+            };
+            if attributes::set_statement_attribute_integer(&statement, ffi::SQL_ATTR_CONCURRENCY, concurrency, "setting cursor concurrency")? != Accepted::Exact {
+                debug!("Driver substituted requested cursor concurrency {:?}", settings.concurrency);
+            }
+        }
+
+        if let Some(max_rows) = settings.max_rows {
+            if attributes::set_statement_attribute_integer(&statement, ffi::SQL_ATTR_MAX_ROWS, max_rows as ffi::SQLULEN, "setting maximum number of rows")? != Accepted::Exact {
+                debug!("Driver substituted requested maximum number of rows {}", max_rows);
+            }
+        }
+
+        if let Some(max_length) = settings.max_length {
+            if attributes::set_statement_attribute_integer(&statement, ffi::SQL_ATTR_MAX_LENGTH, max_length as ffi::SQLULEN, "setting maximum value length")? != Accepted::Exact {
+                debug!("Driver substituted requested maximum value length {}", max_length);
+            }
+        }
+
+        Ok(statement)
     }
 
     /// Query list of tables from given catalog.
@@ -330,8 +1484,10 @@ impl<'h, 'c: 'h, C: Configuration> Handle<'c, C> {
         V: TryFromRow<C>,
     {
         debug!("Getting ODBC tables");
+        let _span = spans::execute(None);
         let statement = self.statement()?;
 
+        let start = Instant::now();
         let (result_set, stats_guard): (ResultSetState<'c, 'c, Allocated>, _) = stats::query_execution(move || {
             statement
                 .tables_str(
@@ -343,20 +1499,63 @@ impl<'h, 'c: 'h, C: Configuration> Handle<'c, C> {
                 .wrap_error_while("executing direct statement")
                 .map(ResultSetState::Data)
         })?;
+        let execution_time = start.elapsed();
+        log_slow_query(&self.connection.settings, "tables", execution_time);
 
         Ok(ResultSet::from_result(
             self,
             result_set,
             stats_guard,
+            execution_time,
             &self.connection.settings,
             self.configuration.clone(),
         )?)
     }
 
+    /// List names of all catalogs (databases) available to the connection.
+    pub fn list_catalogs(&mut self) -> Result<Vec<String>, QueryError> {
+        self.tables_column(SQL_ALL_CATALOGS, "", "", "", TABLES_CATALOG_COLUMN)
+    }
+
+    /// List names of all schemas of current catalog.
+    pub fn list_schemas(&mut self) -> Result<Vec<String>, QueryError> {
+        self.tables_column("", SQL_ALL_SCHEMAS, "", "", TABLES_SCHEMA_COLUMN)
+    }
+
+    /// List table types supported by the data source (e.g. "TABLE", "VIEW", "SYSTEM TABLE").
+    pub fn list_table_types(&mut self) -> Result<Vec<String>, QueryError> {
+        self.tables_column("", "", "", SQL_ALL_TABLE_TYPES, TABLES_TABLE_TYPE_COLUMN)
+    }
+
+    /// Call `SQLTables` with special arguments and collect distinct non-NULL values of given result column in order.
+    fn tables_column(
+        &mut self,
+        catalog: &str,
+        schema: &str,
+        table: &str,
+        table_type: &str,
+        column: usize,
+    ) -> Result<Vec<String>, QueryError> {
+        let mut names: Vec<String> = Vec::new();
+        for row in self
+            .with_configuration(DefaultConfiguration)
+            .tables::<ValueRow>(catalog, Some(schema), Some(table), Some(table_type))?
+        {
+            if let Some(name) = row?.into_iter().nth(column).flatten() {
+                let name = name.to_string();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Ok(names)
+    }
+
     /// Prepare statement for fast execution and parametrization.
     /// For one-off queries it is more efficient to use `query()` function.
     pub fn prepare(&'h mut self, query: &str) -> Result<PreparedStatement<'c>, OdbcError> {
         debug!("Preparing ODBC query: {}", &query);
+        let _span = spans::prepare(query);
 
         let statement = stats::query_preparing(|| -> Result<_, OdbcError> {
             Ok(self
@@ -365,7 +1564,21 @@ impl<'h, 'c: 'h, C: Configuration> Handle<'c, C> {
                 .wrap_error_while("preparing query")?)
         })?;
 
-        Ok(PreparedStatement(statement))
+        Ok(PreparedStatement::from_statement(statement, query.to_owned(), None))
+    }
+
+    /// Prepare statement like `prepare()` and describe its parameters and result columns caching the descriptions in returned
+    /// `PreparedStatement` (see `PreparedStatement::param_schema()` and `PreparedStatement::result_schema()`).
+    ///
+    /// Describing requires extra round trips to the driver so it is only done when requested with this function.
+    pub fn prepare_described(&'h mut self, query: &str) -> Result<PreparedStatement<'c>, QueryError> {
+        let mut statement = self.prepare(query)?;
+
+        let parameters = statement.describe_parameters()?;
+        let columns = statement.schema()?;
+        statement.described = Some(PreparedSchema { parameters, columns });
+
+        Ok(statement)
     }
 
     /// Execute one-off query.
@@ -388,27 +1601,204 @@ impl<'h, 'c: 'h, C: Configuration> Handle<'c, C> {
         F: FnOnce(Binder<'c, 'c, Allocated>) -> Result<Binder<'c, 't, Allocated>, BindError>,
     {
         debug!("Direct ODBC query: {}", &query);
+        let _span = spans::execute(Some(query));
 
-        let statement = stats::query_preparing(|| -> Result<_, QueryError> {
+        let (statement, parameters, _owned) = stats::query_preparing(|| -> Result<_, QueryError> {
             //TODO: this take a long time potentially; can I reuse one for all direct queries?
-            Ok(bind(self.statement()?.into())?.into_inner())
+            Ok(bind(Binder::with_settings(self.statement()?, &self.connection.settings))?.into_parts())
         })?;
 
+        log_parameters(&self.connection.settings, query, &parameters);
+
+        let start = Instant::now();
         let (result_set, stats_guard) = stats::query_execution(move || {
             statement
                 .exec_direct(query)
                 .wrap_error_while("executing direct statement")
-        })?;
+        }).map_err(|err| QueryError::execution_error(err.into(), query))?;
+        let execution_time = start.elapsed();
+        log_slow_query(&self.connection.settings, query, execution_time);
 
         Ok(ResultSet::from_result(
             self,
             result_set,
             stats_guard,
+            execution_time,
             &self.connection.settings,
             self.configuration.clone(),
         )?)
     }
 
+    /// Execute one-off query with named placeholders (e.g. `:id`) bound to values from the map by name.
+    ///
+    /// Placeholders are rewritten with `QueryBuilder::with_named_parameters()`; placeholders without value and values not used
+    /// by the query are reported as `QueryError::NamedParametersError`.
+    pub fn query_with_named_map<V>(
+        &'h mut self,
+        query: &str,
+        parameters: &HashMap<String, Value>,
+    ) -> Result<ResultSet<'h, 'c, V, Executed, C>, QueryError>
+    where
+        V: TryFromRow<C>,
+    {
+        let query = QueryBuilder::with_named_parameters(query, parameters)?;
+        self.query_with_parameters(query.sql(), |q| query.bind(q))
+    }
+
+    /// Execute one-off query binding JSON values to its `?` placeholders in order (with "serde_json" feature).
+    ///
+    /// Values are bound according to their kind as with `Binder::bind_value()`.
+    /// Fails with `QueryError::ParameterCountMismatch` if number of values does not match number of placeholders of the query
+    /// (not counting `?` within quoted strings and comments).
+    #[cfg(feature = "serde_json")]
+    pub fn query_with_json_params<V>(
+        &'h mut self,
+        query: &str,
+        params: &[serde_json::Value],
+    ) -> Result<ResultSet<'h, 'c, V, Executed, C>, QueryError>
+    where
+        V: TryFromRow<C>,
+    {
+        let placeholders = crate::count_placeholders(query);
+        if placeholders != params.len() {
+            return Err(QueryError::ParameterCountMismatch { placeholders, parameters: params.len() })
+        }
+        self.query_with_parameters(query, |q| params.iter().try_fold(q, |q, value| q.bind_value(value)))
+    }
+
+    /// Call stored procedure with ODBC escape sequence `{call procedure(?, ...)}` returning all its result sets and output parameters.
+    ///
+    /// Input parameters are bound first followed by output parameters of given SQL types; procedure parameters need to be
+    /// declared in that order.
+    /// All result sets are fetched (result sets without columns are skipped) before output parameters are read as the drivers
+    /// provide their values only after all results were consumed.
+    ///
+    /// Output parameters are bound with C type matching requested SQL type and are provided as corresponding `Value`
+    /// variant (e.g. `SQL_INTEGER` as `Value::Integer`, `SQL_TIMESTAMP` as `Value::Timestamp`); other types are read as
+    /// `Value::String`. Character values longer than `Settings::output_parameter_max_length` fail with
+    /// `QueryError::OutputParameterTruncated`.
+    ///
+    /// Procedure name is embedded into the query so it needs to be (optionally qualified) identifier like `dbo.find_orders`
+    /// or `[my schema].[find orders]`; otherwise call fails with `QueryError::InvalidProcedureName`.
+    ///
+    /// Note that result sets can only be read if the procedure starts with a result set and not a row count (e.g. use
+    /// `SET NOCOUNT ON` with SQL Server); otherwise they are skipped but output parameters are still read.
+    ///
+    /// ```rust
+    /// use odbc_iter::{Odbc, Value};
+    /// use odbc::ffi::SqlDataType;
+    ///
+    /// let mut connection = Odbc::connect(&std::env::var("DB_CONNECTION_STRING").expect("no DB_CONNECTION_STRING env set")).expect("connect to database");
+    ///
+    /// let result = connection
+    ///     .handle()
+    ///     .call_procedure("dbo.find_orders", &[Some(Value::Integer(42))], &[SqlDataType::SQL_INTEGER])
+    ///     .expect("failed to call procedure");
+    ///
+    /// for (schema, rows) in result.result_sets {
+    ///     println!("{} columns, {} rows", schema.len(), rows.len());
+    /// }
+    /// println!("total: {:?}", result.output_parameters[0]);
+    /// ```
+    pub fn call_procedure(
+        &'h mut self,
+        procedure: &str,
+        inputs: &[Option<Value>],
+        outputs: &[ffi::SqlDataType],
+    ) -> Result<ProcedureResult, QueryError> {
+        if !valid_procedure_name(procedure) {
+            return Err(QueryError::InvalidProcedureName(procedure.to_owned()));
+        }
+
+        let placeholders = vec!["?"; inputs.len() + outputs.len()].join(", ");
+        let query = format!("{{call {}({})}}", procedure, placeholders);
+
+        debug!("Calling ODBC procedure: {}", &query);
+        let _span = spans::execute(Some(&query));
+
+        let handle = self.with_configuration(DefaultConfiguration);
+
+        // values are bound as owned so the statement does not borrow parameters and they can stay bound until output is read
+        let (statement, parameters, _owned) = stats::query_preparing(|| -> Result<_, QueryError> {
+            let binder: Binder<'c, 'c, Allocated> = Binder::with_settings(handle.statement()?, &handle.connection.settings);
+            Ok(inputs
+                .iter()
+                .try_fold(binder, |binder, value| binder.bind_owned_value(value.as_ref()))?
+                .into_parts())
+        })?;
+
+        let max_length = handle.connection.settings.output_parameter_max_length.unwrap_or(OUTPUT_PARAMETER_MAX_LEN);
+        let mut output_parameters = outputs
+            .iter()
+            .map(|sql_type| OutputParameter::new(*sql_type, max_length))
+            .collect::<Vec<_>>();
+
+        for (i, output) in output_parameters.iter_mut().enumerate() {
+            attributes::check(unsafe {
+                ffi::SQLBindParameter(
+                    statement.handle(),
+                    (inputs.len() + i + 1) as ffi::SQLUSMALLINT,
+                    ffi::SQL_PARAM_OUTPUT,
+                    output.c_type,
+                    output.sql_type,
+                    output.column_size,
+                    output.decimal_digits,
+                    output.buffer.as_mut_ptr() as ffi::SQLPOINTER,
+                    output.buffer.len() as ffi::SQLLEN,
+                    output.indicator.as_mut() as *mut ffi::SQLLEN,
+                )
+            }, ffi::SQL_HANDLE_STMT, unsafe { statement.handle() } as ffi::SQLHANDLE, "binding output parameter")?;
+        }
+
+        log_parameters(&handle.connection.settings, &query, &parameters);
+
+        let start = Instant::now();
+        let (result_set, stats_guard) = stats::query_execution(|| {
+            statement
+                .exec_direct(&query)
+                .wrap_error_while("executing procedure call")
+        }).map_err(|err| QueryError::execution_error(err.into(), &query))?;
+        let execution_time = start.elapsed();
+        log_slow_query(&handle.connection.settings, &query, execution_time);
+
+        let mut result_sets = Vec::new();
+        {
+            let mut result_set = ResultSet::<ValueRow, _, _>::from_result_keeping_parameters(
+                &handle,
+                result_set,
+                stats_guard,
+                execution_time,
+                &handle.connection.settings,
+                DefaultConfiguration,
+            )?;
+
+            if result_set.had_result_set() {
+                loop {
+                    if !result_set.schema().is_empty() {
+                        let schema = result_set.schema().to_vec();
+                        let rows = result_set.by_ref().collect::<Result<Vec<_>, _>>()?;
+                        result_sets.push((schema, rows));
+                    }
+                    if !result_set.next_result_set()? {
+                        break;
+                    }
+                }
+            } else {
+                // output parameters are available only after all results were processed
+                result_set.skip_remaining_results()?;
+            }
+            // statement is freed with the result set so the driver no longer references output buffers
+        }
+
+        let output_parameters = output_parameters
+            .iter()
+            .enumerate()
+            .map(|(i, output)| output.value(i))
+            .collect::<Result<_, _>>()?;
+
+        Ok(ProcedureResult { result_sets, output_parameters })
+    }
+
     /// Execute prepared statement without parameters.
     pub fn execute<V>(
         &'h mut self,
@@ -430,23 +1820,97 @@ impl<'h, 'c: 'h, C: Configuration> Handle<'c, C> {
         V: TryFromRow<C>,
         F: FnOnce(Binder<'c, 'c, Prepared>) -> Result<Binder<'c, 't, Prepared>, BindError>,
     {
-        let statement = stats::query_preparing(|| -> Result<_, QueryError> {
-            Ok(bind(statement.0.into())?.into_inner())
+        let _span = spans::execute(None);
+        let PreparedStatement { statement, query, described } = statement;
+        let (statement, parameters, _owned) = stats::query_preparing(|| -> Result<_, QueryError> {
+            Ok(bind(Binder::with_settings(statement, &self.connection.settings))?.into_parts())
         })?;
 
+        log_parameters(&self.connection.settings, &query, &parameters);
+
+        let start = Instant::now();
         let (result_set, stats_guard) = stats::query_execution(move || {
             statement
                 .execute()
                 .wrap_error_while("executing statement")
         })?;
+        let execution_time = start.elapsed();
+        log_slow_query(&self.connection.settings, &query, execution_time);
 
         Ok(ResultSet::from_result(
             self,
             result_set,
             stats_guard,
+            execution_time,
             &self.connection.settings,
             self.configuration.clone(),
-        )?)
+        )?
+        .with_prepared(query, described))
+    }
+
+    /// Bind owned parameter values and execute prepared statement.
+    ///
+    /// Unlike with `execute_with_parameters()` the values don't need to outlive the statement as copies bound to the
+    /// statement are kept until its execution is done.
+    pub fn execute_owned<V>(
+        &'h mut self,
+        statement: PreparedStatement<'c>,
+        params: impl Into<OwnedParams>,
+    ) -> Result<ResultSet<'h, 'c, V, Prepared, C>, QueryError>
+    where
+        V: TryFromRow<C>,
+    {
+        let params = params.into();
+        self.execute_with_parameters(statement, |binder| {
+            params
+                .values
+                .iter()
+                .try_fold(binder, |binder, value| binder.bind_owned_value(value.as_ref()))
+        })
+    }
+
+    /// Check if connection is still usable by running trivial "SELECT 1" query.
+    pub fn ping(&mut self) -> Result<(), QueryError> {
+        self.ping_with("SELECT 1")
+    }
+
+    /// Check if connection is still usable by running given no-op query (e.g. "SELECT 1 FROM DUAL" for databases requiring `FROM` clause).
+    /// Any rows returned by the query are discarded.
+    pub fn ping_with(&mut self, query: &str) -> Result<(), QueryError> {
+        for row in self.with_configuration(DefaultConfiguration).query::<ValueRow>(query)? {
+            row?;
+        }
+        Ok(())
+    }
+
+    /// Value of identity (auto-increment) column generated by last insert on this connection.
+    ///
+    /// The query used depends on the database product reported by `Connection::dbms_name()`:
+    /// * SQL Server - `@@IDENTITY` (note that this includes values generated by triggers; `SCOPE_IDENTITY()` is not available
+    /// across separately executed statements),
+    /// * MySQL and MariaDB - `LAST_INSERT_ID()` (0 meaning there was no insert is returned as `None`),
+    /// * SQLite - `last_insert_rowid()` (0 meaning there was no insert is returned as `None`),
+    /// * PostgreSQL - `lastval()` (fails if no sequence was used in the session yet).
+    ///
+    /// Returns `None` for other databases; use "INSERT ... RETURNING" or similar database specific syntax for these.
+    pub fn last_insert_id(&mut self) -> Result<Option<i64>, QueryError> {
+        let dbms_name = self.connection.dbms_name()?.to_ascii_lowercase();
+
+        let (query, none_if_zero) = if dbms_name.contains("sql server") {
+            ("SELECT CAST(@@IDENTITY AS BIGINT)", false)
+        } else if dbms_name.contains("mysql") || dbms_name.contains("mariadb") {
+            ("SELECT CAST(LAST_INSERT_ID() AS SIGNED)", true)
+        } else if dbms_name.contains("sqlite") {
+            ("SELECT last_insert_rowid()", true)
+        } else if dbms_name.contains("postgres") {
+            ("SELECT lastval()", false)
+        } else {
+            debug!("Getting last insert ID is not supported for {}", dbms_name);
+            return Ok(None)
+        };
+
+        let id = self.with_configuration(DefaultConfiguration).query::<Option<i64>>(query)?.single()?;
+        Ok(id.filter(|id| !(none_if_zero && *id == 0)))
     }
 
     /// Calls "START TRANSACTION"
@@ -498,3 +1962,102 @@ impl<'h, 'c: 'h, C: Configuration> Handle<'c, C> {
         Ok(ret)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn test_describe_query() {
+        assert_eq!(describe_query("  SELECT 1\n"), "SELECT 1");
+
+        let query = format!("SELECT '{}'", "ż".repeat(300));
+        let description = describe_query(&query);
+        let (end, _) = query.char_indices().nth(QUERY_DESCRIPTION_MAX_LEN).unwrap();
+        assert!(description.starts_with(&query[..end]));
+        assert!(description[end..].starts_with("... [query hash: "));
+        assert_eq!(description, describe_query(&query));
+        assert_ne!(description, describe_query(&format!("{}'", query)));
+    }
+
+    #[test]
+    fn test_valid_procedure_name() {
+        assert!(valid_procedure_name("find_orders"));
+        assert!(valid_procedure_name("dbo.find_orders"));
+        assert!(valid_procedure_name("#temp_procedure"));
+        assert!(valid_procedure_name("[my schema].[find ]]orders]"));
+        assert!(valid_procedure_name(r#""my schema"."find ""orders""#));
+        assert!(!valid_procedure_name(""));
+        assert!(!valid_procedure_name("find_orders(1); DROP TABLE orders; --"));
+        assert!(!valid_procedure_name("dbo..find_orders"));
+        assert!(!valid_procedure_name("[find_orders"));
+    }
+
+    #[test]
+    fn test_record_parameters() {
+        assert!(!record_parameters(&Settings::default()));
+        assert!(!record_parameters(&Settings {
+            log_parameters: true,
+            redact_parameters: true,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_owned_params() {
+        let params = OwnedParams::from((42, "foo".to_owned(), 1.5));
+        assert_eq!(
+            params.values(),
+            &[Some(Value::Integer(42)), Some(Value::String("foo".to_owned())), Some(Value::Double(1.5))]
+        );
+
+        let params = OwnedParams::new().push(true).push_null();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params.values(), &[Some(Value::Bit(true)), None]);
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_execute_owned() {
+        let mut connection = crate::tests::connect_sql_server();
+        let mut db = connection.handle();
+
+        let statement = db.prepare("SELECT ?, ?, ?").expect("prepare statement");
+        let row = db
+            .execute_owned::<(i32, String, Option<i32>)>(
+                statement,
+                OwnedParams::new().push(40 + 2).push(format!("foo{}", 1)).push_null(),
+            )
+            .expect("execute statement")
+            .single()
+            .expect("fetch row");
+
+        assert_eq!(row, (42, "foo1".to_owned(), None));
+
+        let statement = db.prepare("SELECT ?").expect("prepare statement");
+        let value = db
+            .execute_owned::<String>(statement, ("bar".repeat(2),))
+            .expect("execute statement")
+            .single()
+            .expect("fetch row");
+
+        assert_eq!(value, "barbar");
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_execution_error_query() {
+        let mut connection = crate::tests::connect_sql_server();
+
+        let err = connection.handle().query::<ValueRow>("SELECT * FROM odbc_iter_no_such_table").unwrap_err();
+        assert_matches!(err, QueryError::ExecutionError(..));
+        assert_eq!(err.query(), Some("SELECT * FROM odbc_iter_no_such_table"));
+        assert_eq!(
+            err.to_string(),
+            "ODBC call failed while executing direct statement: SELECT * FROM odbc_iter_no_such_table"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use crate::query::{BindError, Binder};
+use crate::value::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Error rewriting named placeholders (e.g. `:id`) of a query with `QueryBuilder::with_named_parameters()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NamedParametersError {
+    /// Names of placeholders used in the query that have no value provided.
+    MissingParameters(Vec<String>),
+    /// Names of provided values that are not used by any placeholder of the query.
+    UnusedParameters(Vec<String>),
+}
+
+impl fmt::Display for NamedParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NamedParametersError::MissingParameters(names) => {
+                write!(f, "no values provided for query parameters: {}", names.join(", "))
+            }
+            NamedParametersError::UnusedParameters(names) => {
+                write!(f, "values provided for parameters not used in query: {}", names.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for NamedParametersError {}
+
+/// Builds parametrized query text together with list of values for its placeholders.
+///
+/// Every parameter pushed adds `?` placeholder to the query text so the number and order of placeholders and bound
+/// values always match.
+///
+/// Values of `Value::Decimal` and `Value::Json` are bound as strings.
+///
+/// Example
+/// ========
+/// ```rust
+/// use odbc_iter::{Odbc, QueryBuilder, ValueRow};
+///
+/// // Connect to database using connection string
+/// let connection_string = std::env::var("DB_CONNECTION_STRING")
+///     .expect("DB_CONNECTION_STRING environment not set");
+/// let mut connection = Odbc::connect(&connection_string)
+///     .expect("failed to connect to database");
+///
+/// let mut query = QueryBuilder::new();
+/// query.push_sql("SELECT ").push_param(42).push_sql(" AS foo, ").push_param("bar".to_owned()).push_sql(" AS bar");
+///
+/// let row: ValueRow = connection
+///     .handle()
+///     .query_with_parameters(query.sql(), |q| query.bind(q))
+///     .expect("failed to run query")
+///     .single()
+///     .expect("fetch data");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct QueryBuilder {
+    sql: String,
+    parameters: Vec<Value>,
+}
+
+impl QueryBuilder {
+    pub fn new() -> QueryBuilder {
+        Default::default()
+    }
+
+    /// Append literal SQL fragment to the query text.
+    pub fn push_sql(&mut self, sql: &str) -> &mut QueryBuilder {
+        self.sql.push_str(sql);
+        self
+    }
+
+    /// Append `?` placeholder to the query text and value to be bound to it.
+    pub fn push_param(&mut self, value: impl Into<Value>) -> &mut QueryBuilder {
+        let value = match value.into() {
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(value) => Value::String(value.to_string()),
+            #[cfg(feature = "serde_json")]
+            Value::Json(value) => Value::String(value.to_string()),
+            value => value,
+        };
+
+        self.sql.push('?');
+        self.parameters.push(value);
+        self
+    }
+
+    /// Append comma separated list of placeholders (e.g. for `IN (...)` clause) and values to be bound to them.
+    pub fn push_params<T: Into<Value>>(&mut self, values: impl IntoIterator<Item = T>) -> &mut QueryBuilder {
+        for (i, value) in values.into_iter().enumerate() {
+            if i > 0 {
+                self.sql.push_str(", ");
+            }
+            self.push_param(value);
+        }
+        self
+    }
+
+    /// Build query from SQL text with named placeholders (`:name`) replacing each with `?` placeholder bound to value of that name.
+    ///
+    /// Placeholders within quoted strings and identifiers, `--` comments and `::` casts are left as they are.
+    /// The same name can be used more than once. All used names need to have a value and all values need to be used.
+    pub fn with_named_parameters(sql: &str, parameters: &HashMap<String, Value>) -> Result<QueryBuilder, NamedParametersError> {
+        let mut query = QueryBuilder::new();
+        let mut missing = Vec::new();
+        let mut used = Vec::new();
+
+        let mut chars = sql.char_indices().peekable();
+        let mut start = 0;
+        let mut previous = None;
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\'' | '"' => {
+                    // doubled quote escapes are handled as two adjacent quoted strings
+                    for (_, q) in chars.by_ref() {
+                        if q == c {
+                            break;
+                        }
+                    }
+                }
+                '-' if chars.peek().map(|(_, c)| *c) == Some('-') => {
+                    for (_, n) in chars.by_ref() {
+                        if n == '\n' {
+                            break;
+                        }
+                    }
+                }
+                ':' if previous != Some(':') && chars.peek().map(|(_, c)| c.is_ascii_alphabetic() || *c == '_').unwrap_or(false) => {
+                    let mut end = sql.len();
+                    while let Some(&(j, n)) = chars.peek() {
+                        if n.is_ascii_alphanumeric() || n == '_' {
+                            chars.next();
+                        } else {
+                            end = j;
+                            break;
+                        }
+                    }
+                    let name = &sql[i + 1..end];
+
+                    query.push_sql(&sql[start..i]);
+                    start = end;
+                    match parameters.get(name) {
+                        Some(value) => {
+                            query.push_param(value.clone());
+                        }
+                        None => {
+                            query.push_sql("?");
+                            if !missing.iter().any(|missing| missing == name) {
+                                missing.push(name.to_owned());
+                            }
+                        }
+                    }
+                    used.push(name);
+                    previous = None;
+                    continue;
+                }
+                _ => (),
+            }
+            previous = Some(c);
+        }
+        query.push_sql(&sql[start..]);
+
+        if !missing.is_empty() {
+            return Err(NamedParametersError::MissingParameters(missing));
+        }
+
+        let mut unused = parameters
+            .keys()
+            .filter(|name| !used.contains(&name.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unused.is_empty() {
+            unused.sort();
+            return Err(NamedParametersError::UnusedParameters(unused));
+        }
+
+        Ok(query)
+    }
+
+    /// Query text with placeholders.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Values to be bound to placeholders in order.
+    pub fn parameters(&self) -> &[Value] {
+        &self.parameters
+    }
+
+    /// Bind all parameter values in order.
+    pub fn bind<'h, 't, S>(&'t self, binder: Binder<'h, 'h, S>) -> Result<Binder<'h, 't, S>, BindError>
+    where
+        'h: 't,
+    {
+        self.parameters.iter().try_fold(binder, |binder, value| match value {
+            Value::Bit(value) => binder.bind(value),
+            Value::Tinyint(value) => binder.bind(value),
+            Value::Smallint(value) => binder.bind(value),
+            Value::Integer(value) => binder.bind(value),
+            Value::Bigint(value) => binder.bind(value),
+            Value::Float(value) => binder.bind(value),
+            Value::Double(value) => binder.bind(value),
+            Value::String(value) => binder.bind(value),
+            Value::Timestamp(value) => binder.bind(value),
+            Value::Date(value) => binder.bind(value),
+            Value::Time(value) => binder.bind(value),
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(_) => unreachable!("decimal parameters are stored as strings"),
+            #[cfg(feature = "serde_json")]
+            Value::Json(_) => unreachable!("JSON parameters are stored as strings"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_builder() {
+        let mut query = QueryBuilder::new();
+        query
+            .push_sql("SELECT * FROM foo WHERE a = ")
+            .push_param(1)
+            .push_sql(" AND b IN (")
+            .push_params(vec!["x".to_owned(), "y".to_owned()])
+            .push_sql(")");
+
+        assert_eq!(query.sql(), "SELECT * FROM foo WHERE a = ? AND b IN (?, ?)");
+        assert!(query.parameters() == &[
+            Value::Integer(1),
+            Value::String("x".to_owned()),
+            Value::String("y".to_owned()),
+        ][..]);
+    }
+
+    #[test]
+    fn test_query_builder_named_parameters() {
+        let mut parameters = HashMap::new();
+        parameters.insert("id".to_owned(), Value::Integer(1));
+        parameters.insert("name".to_owned(), Value::String("x".to_owned()));
+
+        let query = QueryBuilder::with_named_parameters(
+            "SELECT ':id', \"a:id\", b::text FROM foo WHERE a = :id AND b = :name -- :other\nOR c = :id",
+            &parameters,
+        )
+        .expect("rewrite placeholders");
+
+        assert_eq!(query.sql(), "SELECT ':id', \"a:id\", b::text FROM foo WHERE a = ? AND b = ? -- :other\nOR c = ?");
+        assert!(query.parameters() == &[
+            Value::Integer(1),
+            Value::String("x".to_owned()),
+            Value::Integer(1),
+        ][..]);
+    }
+
+    #[test]
+    fn test_query_builder_named_parameters_errors() {
+        let mut parameters = HashMap::new();
+        parameters.insert("id".to_owned(), Value::Integer(1));
+        parameters.insert("foo".to_owned(), Value::Integer(2));
+        parameters.insert("bar".to_owned(), Value::Integer(3));
+
+        assert_eq!(
+            QueryBuilder::with_named_parameters("SELECT :id, :baz, :baz", &parameters).unwrap_err(),
+            NamedParametersError::MissingParameters(vec!["baz".to_owned()])
+        );
+        assert_eq!(
+            QueryBuilder::with_named_parameters("SELECT :id", &parameters).unwrap_err(),
+            NamedParametersError::UnusedParameters(vec!["bar".to_owned(), "foo".to_owned()])
+        );
+    }
+}
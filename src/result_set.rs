@@ -1,15 +1,19 @@
 use error_context::prelude::*;
 use log::{debug, log_enabled, trace};
-use odbc::{ColumnDescriptor, DiagnosticRecord, Executed, Prepared, ResultSetState};
-use std::convert::TryFrom;
+use odbc::{ffi, ColumnDescriptor, DiagnosticRecord, Executed, Handle as OdbcHandle, Prepared, ResultSetState};
 use std::error::Error;
 use std::fmt;
 use std::marker::PhantomData;
+use std::time::Duration;
 
-use crate::query::{Handle, PreparedStatement};
-use crate::row::{Settings, Configuration, ColumnType, DatumAccessError, Row, TryFromRow, UnsupportedSqlDataType};
+use crate::attributes;
+use crate::query::{Handle, OpenStatements, PreparedSchema, PreparedStatement, QueryError};
+use crate::row::{Settings, Configuration, CursorType, DefaultConfiguration, ColumnDetails, ColumnInfo, ColumnType, ColumnConvertError, DatumAccessError, Row, TryFromColumn, TryFromRow, UnsupportedSqlDataType, name_unnamed_columns, resolve_duplicate_column_names};
+use crate::value::Value;
+use crate::value_row::{ValueRow, TryFromRowRef, TryFromValueRow};
 use crate::OdbcError;
-use crate::stats::QueryFetchingGuard;
+use crate::stats::{self, QueryFetchingGuard};
+use crate::spans::{self, FetchSpan};
 
 /// Error crating ResultSet iterator.
 #[derive(Debug)]
@@ -17,6 +21,12 @@ use crate::stats::QueryFetchingGuard;
 pub enum ResultSetError {
     OdbcError(DiagnosticRecord, &'static str),
     UnsupportedSqlDataType(UnsupportedSqlDataType),
+    DuplicateColumnName(String),
+    NoSuchColumn(usize),
+    /// Column indices passed to `ResultSet::select_columns()` are not in increasing order or repeat.
+    UnorderedColumns(Vec<usize>),
+    NoSuchColumnName(String),
+    UnexpectedNumberOfColumns { expected: u16, got: usize },
 }
 
 impl fmt::Display for ResultSetError {
@@ -28,6 +38,21 @@ impl fmt::Display for ResultSetError {
             ResultSetError::UnsupportedSqlDataType(_) => {
                 write!(f, "query schema has unsupported data type")
             }
+            ResultSetError::DuplicateColumnName(name) => {
+                write!(f, "query schema has duplicate column name: {}", name)
+            }
+            ResultSetError::NoSuchColumn(index) => {
+                write!(f, "query schema has no column with index {}", index)
+            }
+            ResultSetError::UnorderedColumns(indices) => {
+                write!(f, "selected column indices {:?} are not in increasing order", indices)
+            }
+            ResultSetError::NoSuchColumnName(name) => {
+                write!(f, "query schema has no column named '{}'", name)
+            }
+            ResultSetError::UnexpectedNumberOfColumns { expected, got } => {
+                write!(f, "query schema has {} columns but row type expects {}", got, expected)
+            }
         }
     }
 }
@@ -37,6 +62,11 @@ impl Error for ResultSetError {
         match self {
             ResultSetError::OdbcError(err, _) => Some(err),
             ResultSetError::UnsupportedSqlDataType(err) => Some(err),
+            ResultSetError::DuplicateColumnName(_)
+            | ResultSetError::NoSuchColumn(_)
+            | ResultSetError::UnorderedColumns(_)
+            | ResultSetError::NoSuchColumnName(_)
+            | ResultSetError::UnexpectedNumberOfColumns { .. } => None,
         }
     }
 }
@@ -64,6 +94,10 @@ pub enum DataAccessError {
     DatumAccessError(DatumAccessError),
     FromRowError(Box<dyn Error>),
     UnexpectedNumberOfRows(&'static str),
+    /// Query returned more than one row where at most one was expected.
+    UnexpectedMultipleRows,
+    /// Query returned different number of columns than expected.
+    UnexpectedNumberOfColumns { expected: usize, got: usize },
 }
 
 impl fmt::Display for DataAccessError {
@@ -83,6 +117,14 @@ impl fmt::Display for DataAccessError {
                 "unexpected number of rows returned by query: {}",
                 context
             ),
+            DataAccessError::UnexpectedMultipleRows => {
+                write!(f, "expected at most one row but got more rows")
+            }
+            DataAccessError::UnexpectedNumberOfColumns { expected, got } => write!(
+                f,
+                "unexpected number of columns returned by query: expected {} but got {}",
+                expected, got
+            ),
         }
     }
 }
@@ -94,6 +136,8 @@ impl Error for DataAccessError {
             DataAccessError::DatumAccessError(err) => Some(err),
             DataAccessError::FromRowError(err) => Some(err.as_ref()),
             DataAccessError::UnexpectedNumberOfRows(_) => None,
+            DataAccessError::UnexpectedMultipleRows => None,
+            DataAccessError::UnexpectedNumberOfColumns { .. } => None,
         }
     }
 }
@@ -110,16 +154,442 @@ impl From<DatumAccessError> for DataAccessError {
     }
 }
 
+/// Error accessing data of result set row at given index.
+#[derive(Debug)]
+pub struct IndexedDataAccessError {
+    /// Index of the row that failed (first row is 0)
+    pub index: usize,
+    pub error: DataAccessError,
+}
+
+impl fmt::Display for IndexedDataAccessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to access data of row {}", self.index)
+    }
+}
+
+impl Error for IndexedDataAccessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Iterator adapter that tags each row with its index in the result set.
+///
+/// Created with `ResultSet::enumerate_rows()`.
+#[derive(Debug)]
+pub struct EnumerateRows<I> {
+    inner: I,
+    index: usize,
+}
+
+impl<I, V> Iterator for EnumerateRows<I>
+where
+    I: Iterator<Item = Result<V, DataAccessError>>,
+{
+    type Item = Result<(usize, V), IndexedDataAccessError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some(item
+            .map(|value| (index, value))
+            .map_err(|error| IndexedDataAccessError { index, error }))
+    }
+}
+
+/// Iterator adapter that yields at most given number of rows and then drops the underlying result set closing its cursor.
+///
+/// Created with `ResultSet::take_rows()`.
+#[derive(Debug)]
+pub struct TakeRows<I> {
+    inner: Option<I>,
+    remaining: u64,
+}
+
+impl<I: Iterator> Iterator for TakeRows<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            // dropping result set closes the cursor so the connection is not left busy
+            self.inner = None;
+            return None;
+        }
+        let item = self.inner.as_mut()?.next();
+        self.remaining -= 1;
+        if item.is_none() || self.remaining == 0 {
+            self.inner = None;
+        }
+        item
+    }
+}
+
+/// Error of `MapRows` iterator item: either failure of accessing row data or of the mapping function.
+#[derive(Debug)]
+pub enum MapRowsError<E> {
+    DataAccessError(DataAccessError),
+    MapError(E),
+}
+
+impl<E> fmt::Display for MapRowsError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapRowsError::DataAccessError(_) => write!(f, "failed to access result data"),
+            MapRowsError::MapError(_) => write!(f, "failed to map row"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for MapRowsError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MapRowsError::DataAccessError(err) => Some(err),
+            MapRowsError::MapError(err) => Some(err),
+        }
+    }
+}
+
+impl<E> From<DataAccessError> for MapRowsError<E> {
+    fn from(err: DataAccessError) -> MapRowsError<E> {
+        MapRowsError::DataAccessError(err)
+    }
+}
+
+/// Iterator adapter converting rows with values of columns of given names, in order of the names, regardless of order of
+/// the columns in the query.
+///
+/// Created with `ResultSet::by_name()`.
+#[derive(Debug)]
+pub struct ByName<I, T> {
+    inner: I,
+    order: Vec<usize>,
+    phantom: PhantomData<T>,
+}
+
+impl<I, T> Iterator for ByName<I, T>
+where
+    I: Iterator<Item = Result<ValueRow, DataAccessError>>,
+    T: TryFromValueRow,
+{
+    type Item = Result<T, MapRowsError<T::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let order = &self.order;
+        self.inner.next().map(|row| {
+            let mut row = row?;
+            let values = order.iter().map(|index| row[*index].take()).collect();
+            T::try_from_value_row(values).map_err(MapRowsError::MapError)
+        })
+    }
+}
+
+/// Indices of columns of given names (compared case insensitively) in sorted order and position of column of each name
+/// among them.
+fn column_order(schema: &[ColumnType], names: &[&str]) -> Result<(Vec<usize>, Vec<usize>), ResultSetError> {
+    let indices = names
+        .iter()
+        .map(|name| {
+            schema
+                .iter()
+                .position(|column_type| column_type.name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| ResultSetError::NoSuchColumnName((*name).to_owned()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut selected = indices.clone();
+    selected.sort_unstable();
+    selected.dedup();
+
+    let order = indices
+        .iter()
+        .map(|index| selected.binary_search(index).expect("selected column index"))
+        .collect();
+    Ok((selected, order))
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+mod json_array {
+    use super::*;
+    use crate::row::DefaultConfiguration;
+    use serde::ser::{Serialize, SerializeMap, Serializer};
+    use std::io::{self, Write};
+
+    // rows written between flushes of the writer by `ResultSet::write_json_array()`
+    const JSON_ARRAY_FLUSH_ROWS: u64 = 1000;
+
+    /// Error writing result set as JSON array with `ResultSet::write_json_array()` or converting it to JSON with
+    /// `ResultSet::into_result_json()`.
+    #[derive(Debug)]
+    pub enum WriteJsonError {
+        DataAccessError(DataAccessError),
+        JsonError(serde_json::Error),
+        IoError(io::Error),
+    }
+
+    impl fmt::Display for WriteJsonError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                WriteJsonError::DataAccessError(_) => write!(f, "failed to access result data"),
+                WriteJsonError::JsonError(_) => write!(f, "failed to serialize row as JSON"),
+                WriteJsonError::IoError(_) => write!(f, "failed to write JSON"),
+            }
+        }
+    }
+
+    impl Error for WriteJsonError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                WriteJsonError::DataAccessError(err) => Some(err),
+                WriteJsonError::JsonError(err) => Some(err),
+                WriteJsonError::IoError(err) => Some(err),
+            }
+        }
+    }
+
+    impl From<DataAccessError> for WriteJsonError {
+        fn from(err: DataAccessError) -> WriteJsonError {
+            WriteJsonError::DataAccessError(err)
+        }
+    }
+
+    impl From<serde_json::Error> for WriteJsonError {
+        fn from(err: serde_json::Error) -> WriteJsonError {
+            WriteJsonError::JsonError(err)
+        }
+    }
+
+    impl From<io::Error> for WriteJsonError {
+        fn from(err: io::Error) -> WriteJsonError {
+            WriteJsonError::IoError(err)
+        }
+    }
+
+    /// Row serialized as object with column names as keys.
+    struct JsonRow<'r> {
+        schema: &'r [ColumnType],
+        row: &'r ValueRow,
+    }
+
+    impl<'r> Serialize for JsonRow<'r> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.row.len()))?;
+            for (column_type, value) in self.schema.iter().zip(self.row.iter()) {
+                map.serialize_entry(&column_type.name, value)?;
+            }
+            map.end()
+        }
+    }
+
+    impl<'h, 'c: 'h, S> ResultSet<'h, 'c, ValueRow, S, DefaultConfiguration> {
+        /// Write all remaining rows to given writer as JSON array of objects with column names as keys (with "serde" and
+        /// "serde_json" features).
+        ///
+        /// Rows are serialized as they are fetched so the whole result is never held in memory; the writer is flushed
+        /// periodically and after the closing bracket. Empty result set is written as `[]`.
+        pub fn write_json_array<W: Write>(mut self, mut w: W) -> Result<(), WriteJsonError> {
+            w.write_all(b"[")?;
+
+            let mut rows = 0;
+            while let Some(row) = self.next() {
+                let row = row?;
+                if rows > 0 {
+                    w.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut w, &JsonRow { schema: self.schema(), row: &row })?;
+
+                rows += 1;
+                if rows % JSON_ARRAY_FLUSH_ROWS == 0 {
+                    w.flush()?;
+                }
+            }
+
+            w.write_all(b"]")?;
+            w.flush()?;
+            Ok(())
+        }
+
+        /// Fetch all remaining rows into tabular JSON object `{"columns": [...], "rows": [[...], ...]}` (with "serde" and
+        /// "serde_json" features).
+        ///
+        /// `columns` is array of `ColumnInfo` objects describing the schema and `rows` is array of arrays of row values in
+        /// order of the columns.
+        pub fn into_result_json(self) -> Result<serde_json::Value, WriteJsonError> {
+            let columns = serde_json::to_value(self.column_info())?;
+            let rows = self
+                .map(|row| Ok(serde_json::to_value(row?)?))
+                .collect::<Result<Vec<_>, WriteJsonError>>()?;
+
+            Ok(serde_json::json!({ "columns": columns, "rows": rows }))
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub use json_array::WriteJsonError;
+
+/// Iterator adapter that maps rows with fallible function.
+///
+/// Created with `ResultSet::map_rows()`.
+#[derive(Debug)]
+pub struct MapRows<I, F> {
+    inner: I,
+    f: F,
+}
+
+impl<I, V, U, E, F> Iterator for MapRows<I, F>
+where
+    I: Iterator<Item = Result<V, DataAccessError>>,
+    F: FnMut(V) -> Result<U, E>,
+{
+    type Item = Result<U, MapRowsError<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let f = &mut self.f;
+        self.inner
+            .next()
+            .map(|item| item.map_err(MapRowsError::DataAccessError).and_then(|row| f(row).map_err(MapRowsError::MapError)))
+    }
+}
+
+/// Non-fatal diagnostic message reported by the driver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriverWarning {
+    /// Five character SQLSTATE code (e.g. "01004").
+    pub state: String,
+    /// Driver specific error code.
+    pub native_error: i32,
+    pub message: String,
+}
+
+impl fmt::Display for DriverWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.state, self.message)
+    }
+}
+
+impl Error for DriverWarning {}
+
+// distinct driver warnings kept per result set; further warnings are only logged
+const MAX_WARNINGS: usize = 64;
+
+/// Read diagnostic records of the statement handle if the last call on it returned `SQL_SUCCESS_WITH_INFO`.
+///
+/// Repeated warnings (e.g. reported for every row) are stored once and at most `MAX_WARNINGS` are kept.
+pub(crate) fn collect_warnings(handle: ffi::SQLHSTMT, warnings: &mut Vec<DriverWarning>) {
+    let handle = handle as ffi::SQLHANDLE;
+    if attributes::last_return_code(ffi::SQL_HANDLE_STMT, handle) != Some(ffi::SQL_SUCCESS_WITH_INFO) {
+        return;
+    }
+
+    for record in 1.. {
+        match attributes::get_diagnostic_record(ffi::SQL_HANDLE_STMT, handle, record) {
+            Some(warning) => {
+                if warnings.contains(&warning) {
+                    continue;
+                }
+                if warnings.len() >= MAX_WARNINGS {
+                    debug!("Driver warning (not stored; limit of {} reached): {}", MAX_WARNINGS, warning);
+                    continue;
+                }
+                debug!("Driver warning: {}", warning);
+                warnings.push(warning);
+            }
+            None => return,
+        }
+    }
+}
+
+/// Item of `ResultSets` iterator.
+#[derive(Debug)]
+pub enum ResultSetItem<V> {
+    /// New result set begins; following rows conform to this schema.
+    NewResultSet(Vec<ColumnType>),
+    /// Row of the current result set.
+    Row(V),
+}
+
+/// Iterator over rows of all result sets of multi-result set execution.
+///
+/// Created with `ResultSet::result_sets()`.
+#[derive(Debug)]
+pub struct ResultSets<'h, 'c, V, S, C: Configuration> {
+    result_set: ResultSet<'h, 'c, V, S, C>,
+    started: bool,
+    done: bool,
+}
+
+impl<'h, 'c: 'h, V, S, C: Configuration> Iterator for ResultSets<'h, 'c, V, S, C>
+where
+    V: TryFromRow<C>,
+{
+    type Item = Result<ResultSetItem<V>, QueryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            if !self.result_set.schema.is_empty() {
+                return Some(Ok(ResultSetItem::NewResultSet(self.result_set.schema.clone())));
+            }
+        }
+
+        loop {
+            match self.result_set.next() {
+                Some(Ok(row)) => return Some(Ok(ResultSetItem::Row(row))),
+                Some(Err(err)) => return Some(Err(err.into())),
+                None => match self.result_set.next_result_set() {
+                    Ok(true) if self.result_set.schema.is_empty() => continue,
+                    Ok(true) => return Some(Ok(ResultSetItem::NewResultSet(self.result_set.schema.clone()))),
+                    Ok(false) => {
+                        self.done = true;
+                        return None;
+                    }
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(err));
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Fully owned result set rows and schema detached from the connection.
+///
+/// Created with `ResultSet::into_sendable()`.
+#[derive(Debug, Clone)]
+pub struct SendableResult<V> {
+    pub schema: Vec<ColumnType>,
+    pub rows: Vec<V>,
+}
+
 /// Iterator over result set rows.
 ///
 /// Items of this iterator can be of any type that implements `TryFromRow` that includes common Rust types and tuples.
 pub struct ResultSet<'h, 'c, V, S, C: Configuration> {
     statement: Option<ExecutedStatement<'c, S>>,
     schema: Vec<ColumnType>,
+    /// Properties of each `schema` column not part of `ColumnType`.
+    details: Vec<ColumnDetails>,
     columns: i16,
     settings: &'c Settings,
     configuration: C,
     phantom: PhantomData<&'h V>,
+    warnings: Vec<DriverWarning>,
+    rows_fetched: u64,
+    execution_time: Duration,
+    fetch_span: FetchSpan,
+    /// Query text and schema of executed `PreparedStatement` given back by `close()`.
+    prepared: Option<(String, Option<PreparedSchema>)>,
+    failed: bool,
+    registration: Option<(&'c OpenStatements, usize)>,
+    /// Result set column index of each `schema` column when only some columns are fetched.
+    column_numbers: Option<Vec<u16>>,
     _stats_guard: QueryFetchingGuard,
 }
 
@@ -138,7 +608,17 @@ impl<'h, 'c, V, S, C: Configuration> Drop for ResultSet<'h, 'c, V, S, C> {
     fn drop(&mut self) {
         // We need to make sure statement is dropped; implementing Drop forces use of drop(row_iter) if not consumed before another query
         // Should Statement not impl Drop itself?
-        drop(self.statement.take())
+        if let Some(ExecutedStatement::HasResult(statement)) = self.statement.as_ref() {
+            // close the cursor explicitly (also discarding pending results) so the connection is not left busy by partially consumed result set
+            if unsafe { ffi::SQLFreeStmt(statement.handle(), ffi::SQL_CLOSE) } == ffi::SQL_ERROR {
+                debug!("Failed to close cursor of dropped result set");
+            }
+        }
+        drop(self.statement.take());
+        if let Some((open_statements, key)) = self.registration.take() {
+            open_statements.unregister(key);
+        }
+        self.fetch_span.finish(self.rows_fetched);
     }
 }
 
@@ -147,29 +627,190 @@ enum ExecutedStatement<'c, S> {
     NoResult(odbc::Statement<'c, 'c, S, odbc::NoResult>),
 }
 
+impl<'c, S> ExecutedStatement<'c, S> {
+    fn handle(&self) -> ffi::SQLHSTMT {
+        unsafe {
+            match self {
+                ExecutedStatement::HasResult(statement) => statement.handle(),
+                ExecutedStatement::NoResult(statement) => statement.handle(),
+            }
+        }
+    }
+}
+
+// don't reference parameter data any more
+fn reset_parameters<'c, S>(result: ResultSetState<'c, '_, S>) -> Result<ExecutedStatement<'c, S>, ResultSetError> {
+    Ok(match result {
+        ResultSetState::Data(statement) => ExecutedStatement::HasResult(
+            statement
+                .reset_parameters()
+                .wrap_error_while("reseting bound parameters on statement")?,
+        ),
+        ResultSetState::NoData(statement) => ExecutedStatement::NoResult(
+            statement
+                .reset_parameters()
+                .wrap_error_while("reseting bound parameters on statement")?,
+        ),
+    })
+}
+
+fn describe_columns<S>(statement: &odbc::Statement<'_, '_, S, odbc::HasResult>) -> Result<(Vec<ColumnDescriptor>, i16), ResultSetError> {
+    let columns = statement
+        .num_result_cols()
+        .wrap_error_while("getting number of result columns")?;
+    let odbc_schema = (1..=columns)
+        .map(|i| statement.describe_col(i as u16))
+        .collect::<Result<Vec<ColumnDescriptor>, _>>()
+        .wrap_error_while("getting column descriptiors")?;
+    Ok((odbc_schema, columns))
+}
+
+/// Driver specific type names of result set columns; only queried if there are `Settings::vendor_types` or `Settings::json_type_names`
+/// to match against or for columns of `SQL_UNKNOWN_TYPE` (e.g. DB2 `CLOB`) to recognize character large objects.
+fn column_type_names<S>(statement: &odbc::Statement<'_, '_, S, odbc::HasResult>, odbc_schema: &[ColumnDescriptor], settings: &Settings) -> Vec<Option<String>> {
+    let all_columns = !settings.vendor_types.is_empty() || settings.json_type_names;
+
+    odbc_schema
+        .iter()
+        .zip(1..)
+        .map(|(cd, i)| {
+            if !all_columns && cd.data_type != ffi::SQL_UNKNOWN_TYPE {
+                return None
+            }
+            attributes::get_column_attribute_string(statement, i, ffi::SQL_DESC_TYPE_NAME, "getting column type name")
+                .map_err(|err| debug!("Failed to get type name of column {}: {}", i, err))
+                .ok()
+        })
+        .collect()
+}
+
+/// Table and column that result set column originates from as reported by the driver.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ColumnSource {
+    /// Base table name (`SQL_DESC_BASE_TABLE_NAME`); `None` if not provided (e.g. for computed columns).
+    pub table: Option<String>,
+    /// Base column name (`SQL_DESC_BASE_COLUMN_NAME`); `None` if not provided.
+    pub column: Option<String>,
+    /// `true` if column is auto-increment (identity) column (`SQL_DESC_AUTO_UNIQUE_VALUE`); `None` if not provided.
+    pub auto_increment: Option<bool>,
+}
+
+const SQL_TRUE: ffi::SQLLEN = 1;
+
+fn column_attribute_string<S>(statement: &odbc::Statement<'_, '_, S, odbc::HasResult>, column: i16, attribute: ffi::Desc, context: &'static str) -> Option<String> {
+    attributes::get_column_attribute_string(statement, column as u16, attribute, context)
+        .map_err(|err| debug!("Failed {} of column {}: {}", context, column, err))
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+/// Check the schema does not have more columns than the row type expects if configured with `Settings::strict_column_count`.
+fn check_column_count<V: TryFromRow<C>, C: Configuration>(schema: &[ColumnType], settings: &Settings) -> Result<(), ResultSetError> {
+    match V::expected_columns() {
+        Some(expected) if settings.strict_column_count && schema.len() > usize::from(expected) => {
+            Err(ResultSetError::UnexpectedNumberOfColumns { expected, got: schema.len() })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn column_types(odbc_schema: Vec<ColumnDescriptor>, type_names: Vec<Option<String>>, settings: &Settings) -> Result<(Vec<ColumnType>, Vec<ColumnDetails>), ResultSetError> {
+    if log_enabled!(::log::Level::Trace) {
+        for cd in &odbc_schema {
+            trace!("ODBC query result schema: {} [{:?}] size: {:?} nullable: {:?} decimal_digits: {:?}", cd.name, cd.data_type, cd.column_size, cd.nullable, cd.decimal_digits);
+        }
+    }
+
+    // convert schema here so that when iterating rows we can pass reference to it per row for row type conversion
+    let (mut schema, details): (Vec<_>, Vec<_>) = odbc_schema
+        .into_iter()
+        .zip(type_names.into_iter().chain(std::iter::repeat(None)))
+        .map(|(cd, type_name)| ColumnType::with_settings(cd, type_name, settings))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .unzip();
+
+    if settings.name_unnamed_columns {
+        name_unnamed_columns(&mut schema);
+    }
+
+    resolve_duplicate_column_names(&mut schema, settings.duplicate_column_names)
+        .map_err(ResultSetError::DuplicateColumnName)?;
+
+    Ok((schema, details))
+}
+
 impl<'h, 'c: 'h, V, S, C: Configuration> ResultSet<'h, 'c, V, S, C>
 where
     V: TryFromRow<C>,
 {
     pub(crate) fn from_result(
-        _handle: &'h Handle<'c, C>,
+        handle: &'h Handle<'c, C>,
         result: ResultSetState<'c, '_, S>,
         stats_guard: QueryFetchingGuard,
+        execution_time: Duration,
         settings: &'c Settings,
         configuration: C,
     ) -> Result<ResultSet<'h, 'c, V, S, C>, ResultSetError> {
-        let (odbc_schema, columns, statement) = match result {
-            ResultSetState::Data(statement) => {
-                let columns = statement
-                    .num_result_cols()
-                    .wrap_error_while("getting number of result columns")?;
-                let odbc_schema = (1..=columns)
-                    .map(|i| statement.describe_col(i as u16))
-                    .collect::<Result<Vec<ColumnDescriptor>, _>>()
-                    .wrap_error_while("getting column descriptiors")?;
-                let statement = statement
-                    .reset_parameters()
-                    .wrap_error_while("reseting bound parameters on statement")?; // don't reference parameter data any more
+        Self::from_executed(reset_parameters(result)?, stats_guard, execution_time, settings, configuration)
+            .map(|result_set| result_set.registered(handle.open_statements()))
+    }
+
+    /// Wrap result of statement executed directly with the `odbc` crate to fetch its rows with `TryFromRow` types of this crate.
+    ///
+    /// Statement is not tracked by any `Handle` so it is up to the caller to make sure it does not outlive its connection.
+    /// Bound parameters are reset so they are no longer referenced by the statement.
+    /// As execution was not timed `execution_time()` of wrapped result set is zero.
+    pub fn wrap(
+        result: ResultSetState<'c, '_, S>,
+        settings: &'c Settings,
+        configuration: C,
+    ) -> Result<ResultSet<'h, 'c, V, S, C>, ResultSetError> {
+        Self::from_executed(reset_parameters(result)?, stats::query_fetching(), Duration::default(), settings, configuration)
+    }
+
+    /// Like `from_result()` but keeps parameters bound so that the driver can still write output parameters (available after
+    /// all result sets are consumed).
+    ///
+    /// Parameters can only be bound to buffers not borrowed with the statement (e.g. with raw `SQLBindParameter`) that need to
+    /// outlive the result set.
+    pub(crate) fn from_result_keeping_parameters(
+        handle: &'h Handle<'c, C>,
+        result: ResultSetState<'c, 'c, S>,
+        stats_guard: QueryFetchingGuard,
+        execution_time: Duration,
+        settings: &'c Settings,
+        configuration: C,
+    ) -> Result<ResultSet<'h, 'c, V, S, C>, ResultSetError> {
+        let statement = match result {
+            ResultSetState::Data(statement) => ExecutedStatement::HasResult(statement),
+            ResultSetState::NoData(statement) => ExecutedStatement::NoResult(statement),
+        };
+
+        Self::from_executed(statement, stats_guard, execution_time, settings, configuration)
+            .map(|result_set| result_set.registered(handle.open_statements()))
+    }
+
+    /// Track the statement with the connection until the result set is dropped so it can be freed with
+    /// `Connection::free_statements()` if the result set is leaked.
+    fn registered(mut self, open_statements: &'c OpenStatements) -> Self {
+        if let Some(statement) = self.statement.as_ref() {
+            self.registration = Some((open_statements, open_statements.register(statement.handle())));
+        }
+        self
+    }
+
+    fn from_executed(
+        statement: ExecutedStatement<'c, S>,
+        stats_guard: QueryFetchingGuard,
+        execution_time: Duration,
+        settings: &'c Settings,
+        configuration: C,
+    ) -> Result<ResultSet<'h, 'c, V, S, C>, ResultSetError> {
+        let (odbc_schema, type_names, columns) = match &statement {
+            ExecutedStatement::HasResult(statement) => {
+                let (odbc_schema, columns) = describe_columns(statement)?;
+                let type_names = column_type_names(statement, &odbc_schema, settings);
 
                 if log_enabled!(::log::Level::Debug) {
                     if odbc_schema.is_empty() {
@@ -186,54 +827,356 @@ where
                     }
                 }
 
-                (
-                    odbc_schema,
-                    columns,
-                    ExecutedStatement::HasResult(statement),
-                )
+                (odbc_schema, type_names, columns)
             }
-            ResultSetState::NoData(statement) => {
+            ExecutedStatement::NoResult(_) => {
                 debug!("No data");
-                let statement = statement
-                    .reset_parameters()
-                    .wrap_error_while("reseting bound parameters on statement")?; // don't reference parameter data any more
-                (Vec::new(), 0, ExecutedStatement::NoResult(statement))
+                (Vec::new(), Vec::new(), 0)
             }
         };
 
-        if log_enabled!(::log::Level::Trace) {
-            for cd in &odbc_schema {
-                trace!("ODBC query result schema: {} [{:?}] size: {:?} nullable: {:?} decimal_digits: {:?}", cd.name, cd.data_type, cd.column_size, cd.nullable, cd.decimal_digits);
-            }
-        }
-
-        // convert schema here so that when iterating rows we can pass reference to it per row for row type conversion
-        let schema = odbc_schema
-            .into_iter()
-            .map(TryFrom::try_from)
-            .collect::<Result<Vec<_>, _>>()?;
+        let (schema, details) = column_types(odbc_schema, type_names, settings)?;
+        check_column_count::<V, C>(&schema, settings)?;
 
         Ok(ResultSet {
             statement: Some(statement),
             schema,
+            details,
             columns,
             phantom: PhantomData,
             settings,
             configuration,
+            warnings: Vec::new(),
+            rows_fetched: 0,
+            execution_time,
+            fetch_span: spans::fetch(),
+            prepared: None,
+            failed: false,
+            registration: None,
+            column_numbers: None,
             _stats_guard: stats_guard,
         })
     }
 
+    /// Keep query text and schema described at prepare time so they are available again on `PreparedStatement` returned by
+    /// `close()`.
+    pub(crate) fn with_prepared(mut self, query: String, prepared_schema: Option<PreparedSchema>) -> Self {
+        self.prepared = Some((query, prepared_schema));
+        self
+    }
+
+    fn fetch_row<O>(
+        &mut self,
+        f: impl for<'r, 's> FnOnce(Row<'r, 's, 'c, S, C>) -> Result<O, DataAccessError>,
+    ) -> Option<Result<O, DataAccessError>> {
+        let statement = match self.statement.as_mut().unwrap() {
+            ExecutedStatement::HasResult(statement) => statement,
+            ExecutedStatement::NoResult(_) => return None,
+        };
+
+        // Invalid cursor or stopped after row error
+        if self.columns == 0 || self.failed {
+            return None;
+        }
+
+        let settings = self.settings;
+        let configuration = &self.configuration;
+        let schema = &self.schema;
+        let details = &self.details;
+        let column_numbers = self.column_numbers.as_deref();
+        let warnings = &mut self.warnings;
+        let rows_fetched = &mut self.rows_fetched;
+        let failed = &mut self.failed;
+        let handle = unsafe { statement.handle() };
+
+        statement
+            .fetch()
+            .wrap_error_while("fetching row")
+            .transpose()
+            .map(|cursor| {
+                let cursor = cursor.map_err(|err| {
+                    // cursor may not have advanced so fetching again could fail on the same row forever
+                    *failed = true;
+                    err
+                })?;
+                // fetch warnings (e.g. "01003") are cleared by the first column read; column reads collect their own
+                collect_warnings(handle, warnings);
+                let row = f(Row::new(cursor, schema, settings, configuration)
+                    .with_column_numbers(column_numbers)
+                    .with_details(details)
+                    .with_warnings(handle, warnings));
+                let row = row.map_err(|err| {
+                    *failed = settings.stop_on_row_error;
+                    err
+                })?;
+                *rows_fetched += 1;
+                Ok(row)
+            })
+    }
+
     /// Information about column types.
+    ///
+    /// Schema is described when the result set is created after query execution so it is available before the first row is fetched
+    /// and calling this does not fetch or consume any rows.
     pub fn schema(&self) -> &[ColumnType] {
         self.schema.as_slice()
     }
 
+    /// Serializable description of result set columns including column size and decimal digits provided by the driver.
+    pub fn column_info(&self) -> Vec<ColumnInfo> {
+        self.schema.iter().zip(&self.details).map(|(column_type, details)| ColumnInfo::new(column_type, details)).collect()
+    }
+
+    /// Base table and column names and other extended information about result set columns in order of `schema()` for
+    /// drivers that provide them.
+    ///
+    /// This is queried from the driver on each call; fields are `None` where the driver does not supply them.
+    pub fn column_sources(&self) -> Vec<ColumnSource> {
+        let statement = match self.statement.as_ref().unwrap() {
+            ExecutedStatement::HasResult(statement) => statement,
+            ExecutedStatement::NoResult(_) => return Vec::new(),
+        };
+
+        (1..=self.columns)
+            .map(|i| ColumnSource {
+                table: column_attribute_string(statement, i, ffi::SQL_DESC_BASE_TABLE_NAME, "getting base table name"),
+                column: column_attribute_string(statement, i, ffi::SQL_DESC_BASE_COLUMN_NAME, "getting base column name"),
+                auto_increment: attributes::get_column_attribute_integer(statement, i as u16, ffi::SQL_DESC_AUTO_UNIQUE_VALUE, "getting auto-increment")
+                    .map_err(|err| debug!("Failed getting auto-increment of column {}: {}", i, err))
+                    .ok()
+                    .map(|value| value == SQL_TRUE),
+            })
+            .collect()
+    }
+
+    /// Number of rows successfully fetched and converted so far (across all result sets).
+    pub fn rows_fetched(&self) -> u64 {
+        self.rows_fetched
+    }
+
+    /// Time it took to execute the query until result set was available (not including fetching of rows).
+    pub fn execution_time(&self) -> Duration {
+        self.execution_time
+    }
+
+    /// `true` if the statement produced a result set (e.g. "SELECT", even if it returned no rows) and `false` if it did not
+    /// (e.g. "UPDATE" or "CREATE TABLE").
+    pub fn had_result_set(&self) -> bool {
+        match self.statement.as_ref().unwrap() {
+            ExecutedStatement::HasResult(_) => true,
+            ExecutedStatement::NoResult(_) => false,
+        }
+    }
+
+    /// Distinct non-fatal diagnostic messages reported by the driver while fetching rows and reading their columns so far (e.g.
+    /// "01003" NULL value eliminated in aggregate function or "01004" string data right truncation of any column).
+    ///
+    /// Diagnostics are read after the fetch and after each column read when the driver returned `SQL_SUCCESS_WITH_INFO`
+    /// and at most 64 distinct warnings are kept. Columns read with `Column::with_raw_cursor()` are not checked.
+    /// Note that diagnostics reported on query execution itself are not available as the `odbc` crate executing the query
+    /// makes further calls on the statement (clearing them) before the result set is created.
+    pub fn warnings(&self) -> &[DriverWarning] {
+        &self.warnings
+    }
+
     /// Get associated data access configuration object.
     pub fn configuration(&self) -> &C {
         &self.configuration
     }
 
+    /// Iterate rows together with their index in the result set (first row is 0).
+    /// Unlike `Iterator::enumerate` failed rows also report their index with `IndexedDataAccessError`.
+    pub fn enumerate_rows(self) -> EnumerateRows<Self> {
+        EnumerateRows {
+            inner: self,
+            index: 0,
+        }
+    }
+
+    /// Map each row with fallible function; errors of accessing row data and of the function are merged into `MapRowsError`.
+    pub fn map_rows<U, E, F>(self, f: F) -> MapRows<Self, F>
+    where
+        F: FnMut(V) -> Result<U, E>,
+    {
+        MapRows { inner: self, f }
+    }
+
+    /// Number of the current row of the cursor (`SQL_ATTR_ROW_NUMBER`) counting from 1.
+    ///
+    /// Returns `None` for forward-only cursor (see `Settings::cursor_type`), when the cursor is not positioned on a row, if
+    /// there is no result set or if the driver does not support this attribute.
+    pub fn current_row_number(&self) -> Result<Option<u64>, OdbcError> {
+        let statement = match self.statement.as_ref().unwrap() {
+            ExecutedStatement::HasResult(statement) => statement,
+            ExecutedStatement::NoResult(_) => return Ok(None),
+        };
+
+        if self.settings.cursor_type == CursorType::ForwardOnly {
+            return Ok(None);
+        }
+
+        match attributes::get_statement_attribute_integer(statement, ffi::SQL_ATTR_ROW_NUMBER, "getting current row number") {
+            Ok(0) => Ok(None),
+            Ok(row_number) => Ok(Some(row_number as u64)),
+            Err(err) => {
+                let handle = unsafe { statement.handle() } as ffi::SQLHANDLE;
+                let mut diagnostics = (1..).map_while(|record| attributes::get_diagnostic_record(ffi::SQL_HANDLE_STMT, handle, record));
+                // optional feature not implemented or invalid attribute
+                if diagnostics.any(|diagnostic| diagnostic.state == "HYC00" || diagnostic.state == "HY092") {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    /// Move past all remaining results of the statement until the driver reports there are no more (e.g. row counts of
+    /// statements executed by a procedure) so that values of output parameters become available.
+    ///
+    /// Any result sets found this way are discarded.
+    pub(crate) fn skip_remaining_results(&mut self) -> Result<(), QueryError> {
+        let handle = self.statement.as_ref().unwrap().handle();
+        loop {
+            match unsafe { ffi::SQLMoreResults(handle) } {
+                ffi::SQL_NO_DATA => return Ok(()),
+                ret => attributes::check(ret, ffi::SQL_HANDLE_STMT, handle as ffi::SQLHANDLE, "moving to next result")?,
+            };
+            collect_warnings(handle, &mut self.warnings);
+        }
+    }
+
+    /// Fetch only columns of given indices (in order of the result set) and skip the others so that their data is not
+    /// transferred or decoded.
+    ///
+    /// This is useful when only few columns of wide result set of query that can't be changed (e.g. `SELECT *`) are needed.
+    /// Rows contain selected columns only and `schema()` describes only them. Indices must be given in increasing order as
+    /// ODBC drivers may require accessing column data in order; otherwise this fails with `ResultSetError::UnorderedColumns`
+    /// (see `by_name()` for mapping columns in other order). Fails with `ResultSetError::NoSuchColumn` if index is out of range.
+    /// The selection applies to the current result set only.
+    pub fn select_columns(mut self, indices: &[usize]) -> Result<Self, ResultSetError> {
+        if indices.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(ResultSetError::UnorderedColumns(indices.to_vec()))
+        }
+
+        if let Some(&index) = indices.iter().find(|index| **index >= self.schema.len()) {
+            return Err(ResultSetError::NoSuchColumn(index))
+        }
+
+        // indices may be relative to previous selection
+        let column_numbers = indices
+            .iter()
+            .map(|index| self.column_numbers.as_ref().map(|numbers| numbers[*index]).unwrap_or(*index as u16))
+            .collect();
+        let mut schema = std::mem::take(&mut self.schema).into_iter().map(Some).collect::<Vec<_>>();
+        self.schema = indices.iter().filter_map(|index| schema[*index].take()).collect();
+        self.details = indices.iter().map(|index| self.details[*index].clone()).collect();
+        self.column_numbers = Some(column_numbers);
+        Ok(self)
+    }
+
+    /// Fetch at most `n` rows; once `n` rows were fetched the cursor is closed and no further rows are fetched from the driver.
+    ///
+    /// Unlike `Iterator::take` the result set is released as soon as the limit is reached, not only when the iterator is dropped.
+    /// This guards against accidentally streaming unbounded result; see also `Settings::max_rows` for server-side limit.
+    pub fn take_rows(self, n: u64) -> TakeRows<Self> {
+        TakeRows {
+            inner: Some(self),
+            remaining: n,
+        }
+    }
+
+    /// Advance to the next result set of multi-result set execution (e.g. batch of statements or stored procedure).
+    ///
+    /// Any not consumed rows of the current result set are discarded and `schema()` is updated to describe the next result set.
+    /// Returns `false` if there are no more result sets.
+    /// Note that further result sets are available only if the execution has started with a result set (e.g. not with "INSERT").
+    pub fn next_result_set(&mut self) -> Result<bool, QueryError> {
+        let statement = match self.statement.as_ref().unwrap() {
+            ExecutedStatement::HasResult(statement) => statement,
+            ExecutedStatement::NoResult(_) => return Ok(false),
+        };
+
+        match unsafe { ffi::SQLMoreResults(statement.handle()) } {
+            ffi::SQL_NO_DATA => return Ok(false),
+            ret => attributes::check(
+                ret,
+                ffi::SQL_HANDLE_STMT,
+                unsafe { statement.handle() } as ffi::SQLHANDLE,
+                "moving to next result set",
+            )?,
+        };
+        // e.g. messages of statements executed before the next result set
+        collect_warnings(unsafe { statement.handle() }, &mut self.warnings);
+
+        let (odbc_schema, columns) = describe_columns(statement)?;
+        let type_names = column_type_names(statement, &odbc_schema, self.settings);
+        let (schema, details) = column_types(odbc_schema, type_names, self.settings)?;
+        self.schema = schema;
+        self.details = details;
+        check_column_count::<V, C>(&self.schema, self.settings)?;
+        self.columns = columns;
+        self.column_numbers = None;
+        self.failed = false;
+
+        debug!("Got next result set with {} columns", columns);
+        Ok(true)
+    }
+
+    /// Iterate rows of all result sets of multi-result set execution as single stream.
+    ///
+    /// Each result set with columns is preceded by `ResultSetItem::NewResultSet` item with its schema.
+    /// Result sets without columns (e.g. row counts of "INSERT" statements) are skipped.
+    pub fn result_sets(self) -> ResultSets<'h, 'c, V, S, C> {
+        ResultSets {
+            result_set: self,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Fetch all remaining rows into fully owned `SendableResult` together with the schema.
+    ///
+    /// Once materialized the result is detached from the connection and, for `Send` row types like `ValueRow`, can be moved to
+    /// other threads for processing.
+    pub fn into_sendable(mut self) -> Result<SendableResult<V>, DataAccessError> {
+        let rows = self.by_ref().collect::<Result<Vec<_>, _>>()?;
+        let schema = std::mem::replace(&mut self.schema, Vec::new());
+        Ok(SendableResult { schema, rows })
+    }
+
+    /// Fetch next row into provided buffer reusing its allocation.
+    ///
+    /// The buffer is cleared and overwritten with values of the row on each call regardless of the `ResultSet` item type.
+    /// Returns `false` if there are no more rows.
+    /// This is useful in tight loops to avoid allocating new `ValueRow` for each row.
+    pub fn fetch_into(&mut self, buf: &mut ValueRow) -> Result<bool, DataAccessError> {
+        buf.clear();
+        self.fetch_row(|mut row| {
+            while let Some(column) = row.shift_column() {
+                let value: Option<Value> = TryFromColumn::try_from_column(column)
+                    .map_err(|err: ColumnConvertError| DataAccessError::FromRowError(Box::new(err)))?;
+                buf.push(value);
+            }
+            Ok(())
+        })
+        .transpose()
+        .map(|fetched| fetched.is_some())
+    }
+
+    /// Fetch next row into provided buffer and convert it with `TryFromRowRef` borrowing the buffer.
+    ///
+    /// Returned value borrows the buffer so it has to be dropped before fetching next row into the same buffer.
+    /// Returns `None` if there are no more rows.
+    pub fn fetch_ref<'b, T: TryFromRowRef<'b>>(&mut self, buf: &'b mut ValueRow) -> Result<Option<T>, DataAccessError> {
+        if !self.fetch_into(buf)? {
+            return Ok(None);
+        }
+        T::try_from_row_ref(buf)
+            .map(Some)
+            .map_err(|err| DataAccessError::FromRowError(Box::new(err)))
+    }
+
     /// Get exactly one row from the result set.
     /// This function will fail if zero or more than one rows would be provided.
     pub fn single(mut self) -> Result<V, DataAccessError> {
@@ -248,6 +1191,32 @@ where
         value
     }
 
+    /// Get at most one row from the result set.
+    /// Returns `None` if there were no rows and fails with `DataAccessError::UnexpectedMultipleRows` if there was more than one row.
+    pub fn optional(mut self) -> Result<Option<V>, DataAccessError> {
+        let value = match self.next() {
+            Some(value) => value?,
+            None => return Ok(None),
+        };
+        if self.next().is_some() {
+            return Err(DataAccessError::UnexpectedMultipleRows);
+        }
+        Ok(Some(value))
+    }
+
+    /// Get value of the single column of at most one row from the result set.
+    /// Fails with `DataAccessError::UnexpectedNumberOfColumns` if the result set does not have exactly one column
+    /// and with `DataAccessError::UnexpectedMultipleRows` if there was more than one row.
+    pub fn scalar(self) -> Result<Option<V>, DataAccessError> {
+        if self.schema.len() != 1 {
+            return Err(DataAccessError::UnexpectedNumberOfColumns {
+                expected: 1,
+                got: self.schema.len(),
+            });
+        }
+        self.optional()
+    }
+
     /// Get first row from the result set.
     /// Any following rows are discarded.
     /// This function will fail no rows were provided.
@@ -276,14 +1245,18 @@ where
 {
     /// Close the result set and discard any not consumed rows.
     pub fn close(mut self) -> Result<PreparedStatement<'c>, OdbcError> {
+        // wrapped result sets do not know the query text
+        let (query, prepared_schema) = self.prepared.take().unwrap_or_default();
         match self.statement.take().unwrap() {
             ExecutedStatement::HasResult(statement) => Ok(PreparedStatement::from_statement(
                 statement
                     .close_cursor()
                     .wrap_error_while("closing cursor on executed prepared statement")?,
+                query,
+                prepared_schema,
             )),
             ExecutedStatement::NoResult(statement) => {
-                Ok(PreparedStatement::from_statement(statement))
+                Ok(PreparedStatement::from_statement(statement, query, prepared_schema))
             }
         }
     }
@@ -334,6 +1307,23 @@ where
     }
 }
 
+impl<'h, 'c: 'h, S> ResultSet<'h, 'c, ValueRow, S, DefaultConfiguration> {
+    /// Convert rows to type implementing `TryFromValueRow` from values of columns of given names (compared case
+    /// insensitively) passed in order of the names.
+    ///
+    /// Mapping of names to columns is resolved from the schema once so conversion does not depend on order of columns in
+    /// the query (e.g. "SELECT a, b" or "SELECT b, a"). Other columns are not fetched (see `select_columns()`).
+    /// Fails with `ResultSetError::NoSuchColumnName` if there is no column of given name. Names must be unique.
+    pub fn by_name<T: TryFromValueRow>(self, names: &[&str]) -> Result<ByName<Self, T>, ResultSetError> {
+        let (selected, order) = column_order(self.schema(), names)?;
+        Ok(ByName {
+            inner: self.select_columns(&selected)?,
+            order,
+            phantom: PhantomData,
+        })
+    }
+}
+
 impl<'h, 'c: 'h, V, S, C: Configuration> Iterator for ResultSet<'h, 'c, V, S, C>
 where
     V: TryFromRow<C>,
@@ -341,29 +1331,10 @@ where
     type Item = Result<V, DataAccessError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let statement = match self.statement.as_mut().unwrap() {
-            ExecutedStatement::HasResult(statement) => statement,
-            ExecutedStatement::NoResult(_) => return None,
-        };
-
-        // Invalid cursor
-        if self.columns == 0 {
-            return None;
-        }
-
-        let settings = self.settings;
-        let configuration = &self.configuration;
-        let schema = &self.schema;
-
-        statement
-            .fetch()
-            .wrap_error_while("fetching row")
-            .transpose()
-            .map(|cursor| {
-                let row = Row::new(cursor?, schema, settings, configuration);
-                TryFromRow::try_from_row(row)
-                    .map_err(|err| DataAccessError::FromRowError(Box::new(err)))
-            })
+        self.fetch_row(|row| {
+            TryFromRow::try_from_row(row)
+                .map_err(|err| DataAccessError::FromRowError(Box::new(err)))
+        })
     }
 }
 
@@ -388,6 +1359,180 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_column_order() {
+        use super::{column_order, ResultSetError};
+        use crate::DatumType;
+
+        let schema = ["id", "Name", "extra", "note"]
+            .iter()
+            .map(|name| ColumnType {
+                datum_type: DatumType::String,
+                odbc_type: odbc::ffi::SqlDataType::SQL_VARCHAR,
+                nullable: true,
+                name: (*name).to_owned(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(column_order(&schema, &["id", "name"]).unwrap(), (vec![0, 1], vec![0, 1]));
+        assert_eq!(column_order(&schema, &["note", "id"]).unwrap(), (vec![0, 3], vec![1, 0]));
+        assert_matches!(column_order(&schema, &["id", "other"]), Err(ResultSetError::NoSuchColumnName(ref name)) => assert_eq!(name, "other"));
+    }
+
+    #[test]
+    fn test_enumerate_rows() {
+        use super::{DataAccessError, EnumerateRows};
+
+        let rows = vec![
+            Ok(42),
+            Err(DataAccessError::UnexpectedNumberOfRows("foo")),
+            Ok(24),
+        ];
+
+        let rows = EnumerateRows {
+            inner: rows.into_iter(),
+            index: 0,
+        }
+        .collect::<Vec<_>>();
+
+        assert_matches!(rows[0], Ok((0, 42)));
+        assert_matches!(rows[1], Err(ref err) => assert_eq!(err.index, 1));
+        assert_matches!(rows[2], Ok((2, 24)));
+    }
+
+    #[test]
+    fn test_map_rows() {
+        use super::{DataAccessError, MapRows, MapRowsError};
+
+        let rows = MapRows {
+            inner: vec![Ok(1), Err(DataAccessError::UnexpectedNumberOfRows("foo")), Ok(-1), Ok(2)].into_iter(),
+            f: |value: i32| if value < 0 { Err("negative") } else { Ok(value * 2) },
+        }
+        .collect::<Vec<_>>();
+
+        assert_matches!(rows[0], Ok(2));
+        assert_matches!(rows[1], Err(MapRowsError::DataAccessError(DataAccessError::UnexpectedNumberOfRows("foo"))));
+        assert_matches!(rows[2], Err(MapRowsError::MapError("negative")));
+        assert_matches!(rows[3], Ok(4));
+    }
+
+    #[test]
+    fn test_take_rows() {
+        use super::TakeRows;
+        use std::rc::Rc;
+
+        // stands for result set; holds the reference as long as it is not dropped
+        struct Rows(std::vec::IntoIter<i32>, Rc<()>);
+
+        impl Iterator for Rows {
+            type Item = i32;
+            fn next(&mut self) -> Option<i32> {
+                self.0.next()
+            }
+        }
+
+        let open = Rc::new(());
+        let mut rows = TakeRows {
+            inner: Some(Rows(vec![1, 2, 3].into_iter(), Rc::clone(&open))),
+            remaining: 2,
+        };
+
+        assert_eq!(rows.next(), Some(1));
+        assert_eq!(Rc::strong_count(&open), 2);
+        assert_eq!(rows.next(), Some(2));
+        assert_eq!(Rc::strong_count(&open), 1);
+        assert_eq!(rows.next(), None);
+
+        let rows = TakeRows {
+            inner: Some(vec![1, 2].into_iter()),
+            remaining: 5,
+        }
+        .collect::<Vec<_>>();
+        assert_eq!(rows, [1, 2]);
+
+        let rows = TakeRows {
+            inner: Some(vec![1, 2].into_iter()),
+            remaining: 0,
+        }
+        .collect::<Vec<_>>();
+        assert!(rows.is_empty());
+    }
+
+    #[derive(Debug)]
+    struct Raw {
+        val: String,
+    }
+
+    impl<C: Configuration> TryFromRow<C> for Raw {
+        type Error = DatumAccessError;
+        fn try_from_row<'r, 's, 'c, S>(mut row: Row<'r, 's, 'c, S, C>) -> Result<Self, Self::Error> {
+            let column = row.shift_column().expect("column");
+            let val = column
+                .with_raw_cursor(|cursor, index| cursor.get_data::<String>(index))
+                .map_err(DatumAccessError::OdbcCursorError)?
+                .expect("value");
+            Ok(Raw { val })
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "test-monetdb")]
+    fn test_raw_cursor() {
+        let mut db = crate::tests::connect_monetdb();
+
+        let raw: Raw = db
+            .handle()
+            .query("SELECT CAST(42 AS BIGINT) AS val;")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(raw.val, "42");
+    }
+
+    #[test]
+    #[cfg(feature = "test-sql-server")]
+    fn test_sql_server_raw_cursor_unsupported_type() {
+        let mut connection = crate::tests::connect_sql_server();
+
+        assert_matches!(
+            connection.handle().query::<Raw>("SELECT CAST('<foo/>' AS XML) AS val;"),
+            Err(crate::QueryError::ResultSetError(crate::ResultSetError::UnsupportedSqlDataType(_)))
+        );
+
+        let mut connection = crate::tests::connect_sql_server_with_settings(crate::Settings {
+            read_as: vec![(odbc::ffi::SqlDataType::SQL_SS_XML, crate::DatumType::String)],
+            ..Default::default()
+        });
+
+        let raw: Raw = connection
+            .handle()
+            .query("SELECT CAST('<foo/>' AS XML) AS val;")
+            .expect("failed to run query")
+            .single()
+            .expect("fetch data");
+
+        assert_eq!(raw.val, "<foo/>");
+    }
+
+    #[test]
+    #[cfg(feature = "test-monetdb")]
+    fn test_into_sendable() {
+        let mut db = crate::tests::connect_monetdb();
+
+        let result = db
+            .handle()
+            .query::<ValueRow>("SELECT CAST(42 AS BIGINT) AS val;")
+            .expect("failed to run query")
+            .into_sendable()
+            .expect("fetch data");
+
+        let result = std::thread::spawn(move || result).join().unwrap();
+
+        assert_eq!(result.schema[0].name, "val");
+        assert_matches!(result.rows[0][0], Some(Value::Bigint(42)));
+    }
+
     #[test]
     #[cfg(feature = "test-monetdb")]
     fn test_custom_type() {
@@ -662,6 +1807,55 @@ mod tests {
         assert!(value.2.is_none());
     }
 
+    #[test]
+    #[cfg(feature = "test-sql-server")]
+    #[cfg(feature = "serde")]
+    #[cfg(feature = "serde_json")]
+    fn test_write_json_array() {
+        let mut connection = crate::tests::connect_sql_server();
+        let mut db = connection.handle();
+
+        let mut json = Vec::new();
+        db.query::<ValueRow>("SELECT * FROM (VALUES (1, 'foo'), (2, NULL)) AS t (id, name)")
+            .expect("failed to run query")
+            .write_json_array(&mut json)
+            .expect("write JSON");
+
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!([{"id": 1, "name": "foo"}, {"id": 2, "name": null}])
+        );
+
+        let mut json = Vec::new();
+        db.query::<ValueRow>("SELECT 1 AS id WHERE 1 = 0")
+            .expect("failed to run query")
+            .write_json_array(&mut json)
+            .expect("write JSON");
+
+        assert_eq!(json, b"[]");
+    }
+
+    #[test]
+    #[cfg(feature = "test-sql-server")]
+    #[cfg(feature = "serde")]
+    #[cfg(feature = "serde_json")]
+    fn test_into_result_json() {
+        let mut connection = crate::tests::connect_sql_server();
+
+        let json = connection
+            .handle()
+            .query::<ValueRow>("SELECT * FROM (VALUES (1, 'foo'), (2, NULL)) AS t (id, name)")
+            .expect("failed to run query")
+            .into_result_json()
+            .expect("result JSON");
+
+        assert_eq!(json["columns"].as_array().unwrap().len(), 2);
+        assert_eq!(json["columns"][0]["name"], "id");
+        assert_eq!(json["columns"][0]["sql_type"], "SQL_INTEGER");
+        assert_eq!(json["columns"][1]["name"], "name");
+        assert_eq!(json["rows"], serde_json::json!([[1, "foo"], [2, null]]));
+    }
+
     #[test]
     #[cfg(feature = "test-monetdb")]
     #[cfg(feature = "serde_json")]
@@ -11,6 +11,12 @@ use std::error::Error;
 use std::fmt;
 use std::string::FromUtf16Error;
 use std::convert::TryInto;
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::odbc_type::{DriverDefaultString, Guid};
+use crate::value::Value;
+use crate::result_set::{collect_warnings, DriverWarning};
 
 #[cfg(feature = "rust_decimal")]
 use rust_decimal::Decimal;
@@ -31,6 +37,202 @@ impl Configuration for DefaultConfiguration {}
 pub struct Settings {
     /// When `true` the `ResultSet` iterator will try to fetch strings as UTF-16 (wide) strings before converting them to Rust's UTF-8 `String`.
     pub utf_16_strings: bool,
+    /// When `true` invalid UTF-16 sequences fetched with `utf_16_strings` are replaced with U+FFFD replacement character instead of failing with `DatumAccessError::FromUtf16Error`.
+    pub utf_16_lossy: bool,
+    /// When `true` values of fixed width `CHAR(n)`/`NCHAR(n)` columns (`SQL_CHAR`/`SQL_EXT_WCHAR`) are returned with trailing padding spaces removed.
+    pub trim_char: bool,
+    /// Force columns of given ODBC SQL data type to be read as given `DatumType` instead of the default mapping.
+    /// Conversion of the column data to requested type is done by the driver.
+    /// This is useful when driver misreports the column type (e.g. boolean column reported as `SQL_CHAR` with "1"/"0" values).
+    /// These overrides take precedence over `driver_default_types` and `numbers_as_strings`.
+    pub read_as: Vec<(SqlDataType, DatumType)>,
+    /// When `true` all numeric columns (integer, floating point and decimal) are read as `DatumType::String` using their string
+    /// representation as provided by the driver. This preserves exact formatting and precision for lossless export.
+    pub numbers_as_strings: bool,
+    /// Columns of given ODBC SQL data types are read as `DatumType::String` using driver default C type conversion
+    /// (`SQL_C_DEFAULT`) instead of the mapping of this crate. This may help with drivers that fail to convert some types to
+    /// the C types requested by this crate but it only makes sense for types the driver provides as character data by default.
+    /// Numeric types listed here are read with `SQL_C_DEFAULT` conversion also when `numbers_as_strings` is enabled.
+    pub driver_default_types: Vec<SqlDataType>,
+    /// Unit in which the driver provides fraction of second of `TIMESTAMP` and `TIME` values.
+    /// ODBC specifies nanoseconds but some drivers use other units; fetched values are scaled to nanoseconds.
+    pub fraction_unit: FractionUnit,
+    /// When `true` GUID (`SQL_GUID`) columns are read as raw bytes (`SQL_C_BINARY`) and formatted as canonical string by this
+    /// crate (see `Guid::to_canonical_string()`) instead of using string representation provided by the driver.
+    pub guid_bytes: bool,
+    /// When `true` query text and values of all bound parameters will be logged in single entry at debug level before execution.
+    pub log_parameters: bool,
+    /// When `true` parameter values logged due to `log_parameters` are replaced with "<redacted>" (e.g. for deployments handling PII).
+    pub redact_parameters: bool,
+    /// How to handle result set columns having the same name (e.g. two `id` columns from a join).
+    pub duplicate_column_names: DuplicateColumnNames,
+    /// When `true` connection will be switched to read-only access mode (`SQL_ATTR_ACCESS_MODE`) after connecting.
+    /// Drivers may treat this as a hint only; see `Connection::set_read_only`.
+    pub read_only: bool,
+    /// Type of cursor requested for statements (`SQL_ATTR_CURSOR_TYPE`).
+    pub cursor_type: CursorType,
+    /// Cursor concurrency requested for statements (`SQL_ATTR_CONCURRENCY`).
+    pub concurrency: Concurrency,
+    /// When `true` no more rows are returned by the `ResultSet` after a row that failed to convert.
+    /// By default the failed row is yielded as `Err` and iterating continues with following rows so both good rows and
+    /// failures can be collected. Errors of fetching the row itself always end the iteration.
+    pub stop_on_row_error: bool,
+    /// Maximum number of rows returned by statements (`SQL_ATTR_MAX_ROWS`); `None` or 0 returns all rows.
+    /// The limit is applied by the driver or server so it is a cheap way to cap exploratory queries.
+    pub max_rows: Option<u64>,
+    /// Maximum number of bytes returned for character and binary column values (`SQL_ATTR_MAX_LENGTH`); longer values are
+    /// silently truncated. `None` or 0 returns full values. Drivers may ignore this attribute.
+    pub max_length: Option<u64>,
+    /// Maximum length in bytes of character output parameter values read by `Handle::call_procedure()`; longer values fail
+    /// with `QueryError::OutputParameterTruncated`. `None` uses 4000 bytes.
+    pub output_parameter_max_length: Option<usize>,
+    /// When `true` the connection string completed by the driver (`SQLDriverConnect` output) is captured on connect and
+    /// available redacted from `Connection::resolved_connection_string()`.
+    /// This requires connecting to the database twice so it is disabled by default.
+    pub resolve_connection_string: bool,
+    /// Network packet size in bytes (`SQL_ATTR_PACKET_SIZE`) set before connecting; `None` uses driver default.
+    /// Larger packets may improve throughput of big result sets. Drivers may clamp the value or ignore it.
+    pub packet_size: Option<u32>,
+    /// If set queries taking longer than this to execute are logged at warning level.
+    pub slow_query_threshold: Option<Duration>,
+    /// When `true` reading NULL value from column described by the driver as not nullable fails with
+    /// `DatumAccessError::NullInNonNullableColumn` as it indicates driver or data integrity problem.
+    pub strict_nullability: bool,
+    /// When `true` creating `ResultSet` fails with `ResultSetError::UnexpectedNumberOfColumns` if the query returns more columns
+    /// than the row type consumes (see `TryFromRow::expected_columns()`) as it likely indicates wrong query.
+    /// By default extra columns are ignored by row types that do not check the number of columns themselves.
+    pub strict_column_count: bool,
+    /// When `true` columns with empty name (e.g. computed columns like in "SELECT 42") are named after their position
+    /// (`col_1`, `col_2`, ...) so that every column has stable non-empty name.
+    pub name_unnamed_columns: bool,
+    /// Decoders of vendor specific column types consulted before built-in conversions when reading `Value`.
+    pub vendor_types: Vec<VendorType>,
+    /// Names of columns (compared case insensitively) holding JSON text that should be parsed into `Value::Json`
+    /// (with "serde_json" feature). Values that are not valid JSON are read as `Value::String` and a warning is logged.
+    pub json_columns: Vec<String>,
+    /// When `true` columns of driver specific type named `json` or `jsonb` (e.g. PostgreSQL) are handled like `json_columns`.
+    pub json_type_names: bool,
+    /// Code page of narrow character columns (`SQL_CHAR`, `SQL_VARCHAR`, `SQL_LONGVARCHAR`) used to decode them into `String`
+    /// (with "encoding_rs" feature), e.g. `Some(encoding_rs::WINDOWS_1252)`. Invalid sequences are replaced with U+FFFD.
+    /// `None` reads them as UTF-8.
+    #[cfg(feature = "encoding_rs")]
+    pub narrow_encoding: Option<&'static encoding_rs::Encoding>,
+}
+
+impl Settings {
+    #[cfg(feature = "serde_json")]
+    /// `true` if column of given name and driver specific type name was configured to hold JSON text.
+    pub(crate) fn is_json_column(&self, name: &str, type_name: Option<&str>) -> bool {
+        self.json_columns.iter().any(|column| column.eq_ignore_ascii_case(name))
+            || (self.json_type_names && type_name.map(|type_name| type_name.eq_ignore_ascii_case("json") || type_name.eq_ignore_ascii_case("jsonb")).unwrap_or(false))
+    }
+}
+
+/// Decoder of vendor specific column type value from its string representation as provided by the driver.
+pub type VendorTypeDecoder = fn(String) -> Result<Value, Box<dyn Error + Send + Sync>>;
+
+/// Registration of decoder for vendor specific column type (e.g. PostgreSQL `hstore`) in `Settings::vendor_types`.
+///
+/// Columns are matched on ODBC SQL Data Type and driver specific type name (`SQL_DESC_TYPE_NAME`) compared case insensitively.
+/// Matched columns are represented as `DatumType::String` and read as `Value` using the decoder.
+#[derive(Debug, Clone)]
+pub struct VendorType {
+    pub sql_type: SqlDataType,
+    pub type_name: String,
+    pub decoder: VendorTypeDecoder,
+}
+
+impl VendorType {
+    fn matches(&self, sql_type: SqlDataType, type_name: Option<&str>) -> bool {
+        self.sql_type == sql_type && type_name.map(|type_name| self.type_name.eq_ignore_ascii_case(type_name)).unwrap_or(false)
+    }
+}
+
+/// ODBC cursor type (`SQL_ATTR_CURSOR_TYPE`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CursorType {
+    /// Cursor that only scrolls forward (default).
+    ForwardOnly,
+    /// Cursor with result set data fixed at the time of execution.
+    Static,
+    /// Cursor with fixed set of keys reflecting updates and deletes of the rows.
+    Keyset,
+    /// Cursor reflecting all changes made to the result set rows.
+    Dynamic,
+}
+
+impl Default for CursorType {
+    fn default() -> CursorType {
+        CursorType::ForwardOnly
+    }
+}
+
+/// ODBC cursor concurrency (`SQL_ATTR_CONCURRENCY`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Concurrency {
+    /// Cursor is read-only, no updates are allowed (default).
+    ReadOnly,
+    /// Cursor uses the lowest level of locking sufficient to ensure that the row can be updated.
+    Lock,
+    /// Cursor uses optimistic concurrency control comparing row versions.
+    RowVersion,
+    /// Cursor uses optimistic concurrency control comparing values.
+    Values,
+}
+
+impl Default for Concurrency {
+    fn default() -> Concurrency {
+        Concurrency::ReadOnly
+    }
+}
+
+/// Handling of result set columns with the same name.
+///
+/// Columns with empty name (e.g. computed columns) are not considered duplicates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicateColumnNames {
+    /// Keep column names as provided by the driver.
+    Keep,
+    /// Fail creating `ResultSet` with `ResultSetError::DuplicateColumnName` error.
+    Fail,
+    /// Rename following columns with the same name by appending their occurrence number (e.g. `id`, `id_2`, `id_3`).
+    Rename,
+}
+
+impl Default for DuplicateColumnNames {
+    fn default() -> DuplicateColumnNames {
+        DuplicateColumnNames::Keep
+    }
+}
+
+/// Unit of fraction of second field of `SqlTimestamp` and `SqlSsTime2` values provided by the driver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FractionUnit {
+    /// Billionths of a second as specified by ODBC.
+    Nanoseconds,
+    /// 100 nanosecond intervals (precision of SQL Server `TIME(7)`/`DATETIME2(7)`).
+    HundredNanoseconds,
+    Microseconds,
+    Milliseconds,
+}
+
+impl Default for FractionUnit {
+    fn default() -> FractionUnit {
+        FractionUnit::Nanoseconds
+    }
+}
+
+impl FractionUnit {
+    /// Scale fraction of second given in this unit to nanoseconds; values out of range are clamped to 999999999.
+    pub fn to_nanoseconds(self, fraction: u32) -> u32 {
+        let nanoseconds = match self {
+            FractionUnit::Nanoseconds => fraction,
+            FractionUnit::HundredNanoseconds => fraction.saturating_mul(100),
+            FractionUnit::Microseconds => fraction.saturating_mul(1_000),
+            FractionUnit::Milliseconds => fraction.saturating_mul(1_000_000),
+        };
+        nanoseconds.min(999_999_999)
+    }
 }
 
 /// This error can be returned if database provided column type does not match type requested by
@@ -72,6 +274,12 @@ pub enum DatumAccessError {
     OdbcCursorError(DiagnosticRecord),
     SqlDataTypeMismatch(SqlDataTypeMismatch),
     FromUtf16Error(FromUtf16Error, &'static str),
+    /// NULL value read from column described as not nullable by the driver (with `Settings::strict_nullability`).
+    NullInNonNullableColumn(String),
+    /// Decoder registered with `Settings::vendor_types` failed for given type name.
+    VendorTypeDecoderError(String, Box<dyn Error + Send + Sync>),
+    /// GUID column data read as raw bytes (see `Column::into_guid()`) was not 16 bytes long.
+    InvalidGuidLength(usize),
     #[cfg(feature = "serde_json")]
     JsonError(serde_json::Error),
 }
@@ -90,6 +298,15 @@ impl fmt::Display for DatumAccessError {
                 "failed to create String from UTF-16 column data while {}",
                 context
             ),
+            DatumAccessError::NullInNonNullableColumn(name) => {
+                write!(f, "got NULL value from column '{}' described as not nullable", name)
+            }
+            DatumAccessError::VendorTypeDecoderError(type_name, _) => {
+                write!(f, "failed to decode value of vendor type '{}'", type_name)
+            }
+            DatumAccessError::InvalidGuidLength(length) => {
+                write!(f, "expected 16 bytes of GUID data but got {}", length)
+            }
             #[cfg(feature = "serde_json")]
             DatumAccessError::JsonError(_) => write!(f, "failed to convert data to JSON Value"),
         }
@@ -102,6 +319,9 @@ impl Error for DatumAccessError {
             DatumAccessError::OdbcCursorError(err) => Some(err),
             DatumAccessError::SqlDataTypeMismatch(err) => Some(err),
             DatumAccessError::FromUtf16Error(err, _) => Some(err),
+            DatumAccessError::NullInNonNullableColumn(_) => None,
+            DatumAccessError::InvalidGuidLength(_) => None,
+            DatumAccessError::VendorTypeDecoderError(_, err) => Some(err.as_ref()),
             #[cfg(feature = "serde_json")]
             DatumAccessError::JsonError(err) => Some(err),
         }
@@ -134,6 +354,77 @@ pub struct ColumnType {
     pub name: String,
 }
 
+/// Properties of result set column as provided by the ODBC driver that are not part of `ColumnType`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ColumnDetails {
+    /// Column size (e.g. maximum length of string or precision of numeric column); `None` for unbounded large object columns
+    /// (e.g. "VARCHAR(MAX)") for which drivers report 0 or sentinel size.
+    pub(crate) column_size: Option<usize>,
+    /// Number of decimal digits (scale) of the column.
+    pub(crate) decimal_digits: Option<u16>,
+    /// Driver specific type name (e.g. "hstore"); only queried if `Settings::vendor_types` is not empty or `Settings::json_type_names` is set.
+    pub(crate) type_name: Option<String>,
+}
+
+impl ColumnDetails {
+    fn new(column_descriptor: &ColumnDescriptor, type_name: Option<String>) -> ColumnDetails {
+        ColumnDetails {
+            column_size: bounded_column_size(column_descriptor.column_size),
+            decimal_digits: column_descriptor.decimal_digits,
+            type_name,
+        }
+    }
+}
+
+/// Column metadata suitable for serialization (e.g. to send result schema to a frontend as JSON along with rows).
+///
+/// Created with `ResultSet::column_info()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnInfo {
+    /// Name of the column.
+    pub name: String,
+    /// ODBC SQL Data Type name (e.g. "SQL_VARCHAR").
+    pub sql_type: String,
+    /// `true` if column can contain `NULL` value.
+    pub nullable: bool,
+    /// Column size (e.g. maximum length of string or precision of numeric column) if provided by the ODBC driver.
+    /// `None` for unbounded large object columns (e.g. "VARCHAR(MAX)") for which drivers report 0 or sentinel size;
+    /// values of such columns are read in chunks of any length.
+    pub column_size: Option<usize>,
+    /// Number of decimal digits (scale) of the column if provided by the ODBC driver.
+    pub decimal_digits: Option<u16>,
+}
+
+impl ColumnInfo {
+    pub(crate) fn new(column_type: &ColumnType, details: &ColumnDetails) -> ColumnInfo {
+        ColumnInfo {
+            name: column_type.name.clone(),
+            sql_type: format!("{:?}", column_type.odbc_type),
+            nullable: column_type.nullable,
+            column_size: details.column_size,
+            decimal_digits: details.decimal_digits,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ColumnInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ColumnInfo", 5)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("sql_type", &self.sql_type)?;
+        state.serialize_field("nullable", &self.nullable)?;
+        state.serialize_field("column_size", &self.column_size)?;
+        state.serialize_field("decimal_digits", &self.decimal_digits)?;
+        state.end()
+    }
+}
+
 /// Types of values that column can be converted to.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DatumType {
@@ -196,6 +487,8 @@ impl TryFrom<ColumnDescriptor> for ColumnType {
     fn try_from(column_descriptor: ColumnDescriptor) -> Result<ColumnType, UnsupportedSqlDataType> {
         use SqlDataType::*;
         let datum_type = match column_descriptor.data_type {
+            // BIT(n) with n > 1 is a bit string (e.g. MySQL, PostgreSQL) and not a boolean
+            SQL_EXT_BIT if column_descriptor.column_size.map(|size| size > 1).unwrap_or(false) => DatumType::String,
             SQL_EXT_BIT => DatumType::Bit,
             SQL_EXT_TINYINT => DatumType::Tinyint,
             SQL_SMALLINT => DatumType::Smallint,
@@ -205,11 +498,20 @@ impl TryFrom<ColumnDescriptor> for ColumnType {
             SQL_DOUBLE => DatumType::Double,
             #[cfg(feature = "rust_decimal")]
             SQL_DECIMAL | SQL_NUMERIC => DatumType::Decimal,
+            // exact decimal string representation as provided by the driver (e.g. SQL Server MONEY/SMALLMONEY)
+            #[cfg(not(feature = "rust_decimal"))]
+            SQL_DECIMAL | SQL_NUMERIC => DatumType::String,
             SQL_CHAR | SQL_VARCHAR | SQL_EXT_LONGVARCHAR | SQL_EXT_WCHAR | SQL_EXT_WVARCHAR
             | SQL_EXT_WLONGVARCHAR => DatumType::String,
             SQL_TIMESTAMP => DatumType::Timestamp,
             SQL_DATE => DatumType::Date,
             SQL_TIME | SQL_SS_TIME2 => DatumType::Time,
+            // binary data (e.g. SQL Server ROWVERSION/TIMESTAMP version stamps) is read as hex string
+            SQL_EXT_BINARY | SQL_EXT_VARBINARY | SQL_EXT_LONGVARBINARY => DatumType::String,
+            // SQL Server DATETIMEOFFSET is read as RFC 3339 string preserving the offset
+            SQL_SS_TIMESTAMPOFFSET => DatumType::String,
+            // raw bytes are available with `Column::into_guid()`
+            SQL_EXT_GUID => DatumType::String,
             SQL_UNKNOWN_TYPE => {
                 #[cfg(feature = "serde_json")]
                 {
@@ -232,6 +534,157 @@ impl TryFrom<ColumnDescriptor> for ColumnType {
     }
 }
 
+fn is_numeric(sql_type: SqlDataType) -> bool {
+    use SqlDataType::*;
+    match sql_type {
+        SQL_EXT_TINYINT | SQL_SMALLINT | SQL_INTEGER | SQL_EXT_BIGINT | SQL_REAL | SQL_FLOAT | SQL_DOUBLE | SQL_DECIMAL
+        | SQL_NUMERIC => true,
+        _ => false,
+    }
+}
+
+// Drivers report sizes of these or bigger for large object types, e.g. SQL Server "NTEXT" (1073741823 characters) or "TEXT"
+// (2147483647 bytes).
+const UNBOUNDED_COLUMN_SIZE: odbc::ffi::SQLULEN = (i32::MAX / 2) as odbc::ffi::SQLULEN;
+
+/// Column size reported by the driver with 0 or sentinel sizes of large object columns mapped to `None` (unbounded).
+fn bounded_column_size(column_size: Option<odbc::ffi::SQLULEN>) -> Option<usize> {
+    column_size
+        .filter(|size| *size != 0 && *size < UNBOUNDED_COLUMN_SIZE)
+        .map(|size| size as usize)
+}
+
+impl ColumnType {
+    /// Create `ColumnType` and `ColumnDetails` from ODBC column descriptor and driver specific type name applying
+    /// `Settings::vendor_types` and `Settings::read_as` overrides.
+    pub(crate) fn with_settings(column_descriptor: ColumnDescriptor, type_name: Option<String>, settings: &Settings) -> Result<(ColumnType, ColumnDetails), UnsupportedSqlDataType> {
+        let datum_type = if settings.vendor_types.iter().any(|vendor_type| vendor_type.matches(column_descriptor.data_type, type_name.as_deref())) {
+            Some(DatumType::String)
+        } else {
+            json_datum_type(&column_descriptor.name, type_name.as_deref(), settings)
+                .or_else(|| settings.read_as.iter().find(|(sql_type, _)| *sql_type == column_descriptor.data_type).map(|(_, datum_type)| *datum_type))
+                .or_else(|| if settings.driver_default_types.contains(&column_descriptor.data_type) { Some(DatumType::String) } else { None })
+                .or_else(|| if column_descriptor.data_type == SqlDataType::SQL_UNKNOWN_TYPE && character_large_object(type_name.as_deref()).is_some() { Some(DatumType::String) } else { None })
+                .or_else(|| if settings.numbers_as_strings && is_numeric(column_descriptor.data_type) { Some(DatumType::String) } else { None })
+        };
+
+        let details = ColumnDetails::new(&column_descriptor, type_name);
+        if let Some(datum_type) = datum_type {
+            return Ok((ColumnType {
+                datum_type,
+                odbc_type: column_descriptor.data_type,
+                nullable: column_descriptor.nullable.unwrap_or(true),
+                name: column_descriptor.name,
+            }, details))
+        }
+        ColumnType::try_from(column_descriptor).map(|column_type| (column_type, details))
+    }
+}
+
+/// `Some(true)` for national (wide) and `Some(false)` for narrow character large object driver specific type names.
+///
+/// Some drivers (e.g. DB2) report these as types without ODBC SQL data type equivalent and so they are described as
+/// `SQL_UNKNOWN_TYPE`; others (e.g. Oracle) report them as `SQL_EXT_LONGVARCHAR`/`SQL_EXT_WLONGVARCHAR`.
+fn character_large_object(type_name: Option<&str>) -> Option<bool> {
+    let type_name = type_name?;
+    if ["CLOB", "CHARACTER LARGE OBJECT", "CHAR LARGE OBJECT"].iter().any(|name| type_name.eq_ignore_ascii_case(name)) {
+        Some(false)
+    } else if ["NCLOB", "DBCLOB", "NATIONAL CHARACTER LARGE OBJECT"].iter().any(|name| type_name.eq_ignore_ascii_case(name)) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "serde_json")]
+fn json_datum_type(name: &str, type_name: Option<&str>, settings: &Settings) -> Option<DatumType> {
+    if settings.is_json_column(name, type_name) {
+        Some(DatumType::Json)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(feature = "serde_json"))]
+fn json_datum_type(_name: &str, _type_name: Option<&str>, _settings: &Settings) -> Option<DatumType> {
+    None
+}
+
+/// Name columns with empty name after their position (1 based) in the schema (e.g. `col_1`); names already taken by other columns
+/// are avoided by appending occurrence number (e.g. `col_1_2`).
+pub(crate) fn name_unnamed_columns(schema: &mut [ColumnType]) {
+    let mut names: HashSet<String> = schema.iter().map(|column_type| column_type.name.clone()).collect();
+
+    for (index, column_type) in schema.iter_mut().enumerate().filter(|(_, column_type)| column_type.name.is_empty()) {
+        let name = format!("col_{}", index + 1);
+        let new_name = if names.contains(&name) {
+            (2..)
+                .map(|occurrence| format!("{}_{}", name, occurrence))
+                .find(|name| !names.contains(name))
+                .unwrap()
+        } else {
+            name
+        };
+
+        names.insert(new_name.clone());
+        column_type.name = new_name;
+    }
+}
+
+/// Apply `DuplicateColumnNames` handling to the schema.
+/// Returns name of first duplicated column as error if handling is `DuplicateColumnNames::Fail`.
+pub(crate) fn resolve_duplicate_column_names(schema: &mut [ColumnType], handling: DuplicateColumnNames) -> Result<(), String> {
+    if handling == DuplicateColumnNames::Keep {
+        return Ok(())
+    }
+
+    let mut names: HashSet<String> = schema.iter().map(|column_type| column_type.name.clone()).collect();
+    let mut seen = HashSet::new();
+
+    for column_type in schema.iter_mut().filter(|column_type| !column_type.name.is_empty()) {
+        if seen.insert(column_type.name.clone()) {
+            continue
+        }
+
+        if handling == DuplicateColumnNames::Fail {
+            return Err(column_type.name.clone())
+        }
+
+        let new_name = (2..)
+            .map(|occurrence| format!("{}_{}", column_type.name, occurrence))
+            .find(|name| !names.contains(name))
+            .unwrap();
+
+        names.insert(new_name.clone());
+        seen.insert(new_name.clone());
+        column_type.name = new_name;
+    }
+
+    Ok(())
+}
+
+/// Lower case hex representation of binary data; values of the same length compare in the same order as the bytes.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Convert SQL Server `DATETIMEOFFSET` string representation (e.g. "2018-08-24 10:22:33.1230000 +02:00") to RFC 3339
+/// (e.g. "2018-08-24T10:22:33.123+02:00"); value in unexpected format is returned as is.
+fn timestamp_offset_to_rfc3339(value: &str) -> String {
+    let mut parts = value.trim().splitn(3, ' ');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(date), Some(time), Some(offset)) => {
+            let time = if time.contains('.') {
+                time.trim_end_matches('0').trim_end_matches('.')
+            } else {
+                time
+            };
+            format!("{}T{}{}", date, time, offset.trim())
+        }
+        _ => value.to_owned(),
+    }
+}
+
 /// Represents SQL table column which can be converted to Rust native type.
 pub struct Column<'r, 's, 'c, S, C: Configuration> {
     /// Type information about this column
@@ -244,6 +697,10 @@ pub struct Column<'r, 's, 'c, S, C: Configuration> {
     cursor: &'r mut odbc::Cursor<'s, 'c, 'c, S>,
     /// Which column are we at
     index: u16,
+    /// Properties of this column not part of `column_type` if provided by `ResultSet`
+    details: Option<&'r ColumnDetails>,
+    /// Statement handle and warnings of `ResultSet` to add diagnostics of reading this column to
+    warnings: Option<(odbc::ffi::SQLHSTMT, &'r mut Vec<DriverWarning>)>,
 }
 
 impl<'r, 's, 'c, S, C: Configuration> fmt::Debug for Column<'r, 's, 'c, S, C> {
@@ -259,9 +716,50 @@ impl<'r, 's, 'c, S, C: Configuration> fmt::Debug for Column<'r, 's, 'c, S, C> {
 
 impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     fn into<T: OdbcType<'r>>(self) -> Result<Option<T>, DatumAccessError> {
-        self.cursor
+        let value = self.cursor
             .get_data::<T>(self.index + 1)
-            .map_err(DatumAccessError::OdbcCursorError)
+            .map_err(DatumAccessError::OdbcCursorError);
+
+        // each column read replaces diagnostics of the previous one (e.g. "01004" string data right truncation)
+        if let Some((handle, warnings)) = self.warnings {
+            collect_warnings(handle, warnings);
+        }
+        let value = value?;
+
+        if value.is_none() && self.settings.strict_nullability && !self.column_type.nullable {
+            return Err(DatumAccessError::NullInNonNullableColumn(self.column_type.name.clone()))
+        }
+
+        Ok(value)
+    }
+
+    // read as bytes so that data of wrong length is not turned into plausible looking GUID
+    fn into_guid_bytes(self) -> Result<Option<Guid>, DatumAccessError> {
+        self.into::<&[u8]>()?
+            .map(|bytes| Guid::from_slice(bytes).ok_or(DatumAccessError::InvalidGuidLength(bytes.len())))
+            .transpose()
+    }
+
+    /// Driver specific type name of this column if queried (see `ColumnDetails::type_name`).
+    fn type_name(&self) -> Option<&'r str> {
+        self.details.and_then(|details| details.type_name.as_deref())
+    }
+
+    /// Decoder registered with `Settings::vendor_types` for this column if any.
+    pub(crate) fn vendor_type_decoder(&self) -> Option<VendorTypeDecoder> {
+        let odbc_type = self.column_type.odbc_type;
+        let type_name = self.type_name();
+        self.settings.vendor_types.iter()
+            .find(|vendor_type| vendor_type.matches(odbc_type, type_name))
+            .map(|vendor_type| vendor_type.decoder)
+    }
+
+    /// Read column value as string and decode it with given vendor type decoder.
+    pub(crate) fn into_vendor_value(self, decoder: VendorTypeDecoder) -> Result<Option<Value>, DatumAccessError> {
+        let type_name = self.type_name().unwrap_or_default().to_owned();
+        self.into::<String>()?
+            .map(|data| decoder(data).map_err(|err| DatumAccessError::VendorTypeDecoderError(type_name, err)))
+            .transpose()
     }
 
     // https://docs.microsoft.com/en-us/sql/odbc/reference/appendixes/c-data-types?view=sql-server-2017
@@ -269,7 +767,8 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     /// Reads `bool` value from column.
     pub fn into_bool(self) -> Result<Option<bool>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
-            SqlDataType::SQL_EXT_BIT => self.into::<u8>()?.map(|byte| byte != 0),
+            // SQL_EXT_BIT or other type forced with `Settings::read_as`
+            _ if self.column_type.datum_type == DatumType::Bit => self.into::<u8>()?.map(|byte| byte != 0),
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "BIT",
@@ -283,6 +782,7 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     pub fn into_i8(self) -> Result<Option<i8>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_EXT_TINYINT => self.into::<i8>()?,
+            _ if self.column_type.datum_type == DatumType::Tinyint => self.into::<i8>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "TINYINT",
@@ -296,6 +796,7 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     pub fn into_i16(self) -> Result<Option<i16>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_SMALLINT => self.into::<i16>()?,
+            _ if self.column_type.datum_type == DatumType::Smallint => self.into::<i16>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "SMALLINT",
@@ -309,6 +810,7 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     pub fn into_i32(self) -> Result<Option<i32>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_INTEGER => self.into::<i32>()?,
+            _ if self.column_type.datum_type == DatumType::Integer => self.into::<i32>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "INTEGER",
@@ -322,6 +824,7 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     pub fn into_i64(self) -> Result<Option<i64>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_EXT_BIGINT => self.into::<i64>()?,
+            _ if self.column_type.datum_type == DatumType::Bigint => self.into::<i64>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "BIGINT",
@@ -335,6 +838,7 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     pub fn into_f32(self) -> Result<Option<f32>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_REAL | SqlDataType::SQL_FLOAT => self.into::<f32>()?,
+            _ if self.column_type.datum_type == DatumType::Float => self.into::<f32>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "FLOAT",
@@ -348,6 +852,7 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     pub fn into_f64(self) -> Result<Option<f64>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_DOUBLE => self.into::<f64>()?,
+            _ if self.column_type.datum_type == DatumType::Double => self.into::<f64>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "DOUBLE",
@@ -378,47 +883,97 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
         })
     }
 
+    /// Read narrow character column decoding it according to `Settings::narrow_encoding`.
+    fn into_narrow_string(self) -> Result<Option<String>, DatumAccessError> {
+        #[cfg(feature = "encoding_rs")]
+        {
+            if let Some(encoding) = self.settings.narrow_encoding {
+                // raw bytes as stored in the database code page
+                return Ok(self.into::<&[u8]>()?.map(|bytes| encoding.decode_without_bom_handling(bytes).0.into_owned()));
+            }
+        }
+        self.into::<String>()
+    }
+
     /// Reads `String` value from column.
     pub fn into_string(self) -> Result<Option<String>, DatumAccessError> {
         use SqlDataType::*;
-        Ok(match self.column_type.odbc_type {
-            SQL_CHAR | SQL_VARCHAR | SQL_EXT_LONGVARCHAR => self.into::<String>()?,
+        let trim_padding = self.settings.trim_char && match self.column_type.odbc_type {
+            SQL_CHAR | SQL_EXT_WCHAR => true,
+            _ => false,
+        };
+
+        let value = match self.column_type.odbc_type {
+            queried if self.settings.driver_default_types.contains(&queried) => self.into::<DriverDefaultString>()?.map(|value| value.0),
+            SQL_CHAR | SQL_VARCHAR | SQL_EXT_LONGVARCHAR => self.into_narrow_string()?,
+            // character large object values are read in chunks as their size is not known upfront
+            SQL_UNKNOWN_TYPE if character_large_object(self.type_name()) == Some(false) => self.into_narrow_string()?,
+            // multi-bit BIT(n) column as string of bits provided by the driver
+            SQL_EXT_BIT if self.column_type.datum_type == DatumType::String => self.into::<String>()?,
+            // decimal string representation as provided by the driver (exact)
+            SQL_DECIMAL | SQL_NUMERIC => self.into::<String>()?,
+            SQL_SS_TIMESTAMPOFFSET => self.into::<String>()?.map(|value| timestamp_offset_to_rfc3339(&value)),
+            SQL_EXT_GUID if self.settings.guid_bytes => self.into_guid_bytes()?.map(|guid| guid.to_canonical_string()),
+            // note that SQL Server ROWVERSION (aka TIMESTAMP) is binary version stamp and not date/time value
+            SQL_EXT_BINARY | SQL_EXT_VARBINARY | SQL_EXT_LONGVARBINARY => self.into::<&[u8]>()?.map(to_hex),
             SQL_EXT_WCHAR | SQL_EXT_WVARCHAR | SQL_EXT_WLONGVARCHAR |
             SQL_UNKNOWN_TYPE => {
+                // `None` is NULL while zero length slice is an empty string
                 if self.settings.utf_16_strings {
-                    self.into::<&[u16]>()?
-                        .map(|bytes| String::from_utf16(bytes).wrap_error_while("getting UTF-16 string (SQL_EXT_WCHAR | SQL_EXT_WVARCHAR | SQL_EXT_WLONGVARCHAR)"))
-                        .transpose()?
+                    if self.settings.utf_16_lossy {
+                        self.into::<&[u16]>()?.map(String::from_utf16_lossy)
+                    } else {
+                        self.into::<&[u16]>()?
+                            .map(|bytes| String::from_utf16(bytes).wrap_error_while("getting UTF-16 string (SQL_EXT_WCHAR | SQL_EXT_WVARCHAR | SQL_EXT_WLONGVARCHAR)"))
+                            .transpose()?
+                    }
                 } else {
                     self.into::<String>()?
                 }
             }
+            _ if self.column_type.datum_type == DatumType::String => self.into::<String>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "STRING",
                     queried,
                 }))
             }
+        };
+
+        Ok(if trim_padding {
+            value.map(|mut value| {
+                value.truncate(value.trim_end_matches(' ').len());
+                value
+            })
+        } else {
+            value
         })
     }
 
     /// Reads `SqlTimestamp` value from column.
     pub fn into_timestamp(self) -> Result<Option<SqlTimestamp>, DatumAccessError> {
+        let fraction_unit = self.settings.fraction_unit;
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_TIMESTAMP => self.into::<SqlTimestamp>()?,
+            _ if self.column_type.datum_type == DatumType::Timestamp => self.into::<SqlTimestamp>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "TIMESTAMP",
                     queried,
                 }))
             }
-        })
+        }
+        .map(|mut value| {
+            value.fraction = fraction_unit.to_nanoseconds(value.fraction);
+            value
+        }))
     }
 
     /// Reads `SqlDate` value from column.
     pub fn into_date(self) -> Result<Option<SqlDate>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_DATE => self.into::<SqlDate>()?,
+            _ if self.column_type.datum_type == DatumType::Date => self.into::<SqlDate>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "DATE",
@@ -430,6 +985,7 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
 
     /// Reads `SqlSsTime2` value from column.
     pub fn into_time(self) -> Result<Option<SqlSsTime2>, DatumAccessError> {
+        let fraction_unit = self.settings.fraction_unit;
         Ok(match self.column_type.odbc_type {
             SqlDataType::SQL_TIME => self.into::<SqlTime>()?.map(|ss| SqlSsTime2 {
                 hour: ss.hour,
@@ -438,12 +994,30 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
                 fraction: 0,
             }),
             SqlDataType::SQL_SS_TIME2 => self.into::<SqlSsTime2>()?,
+            _ if self.column_type.datum_type == DatumType::Time => self.into::<SqlSsTime2>()?,
             queried => {
                 return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
                     requested: "TIME",
                     queried,
                 }))
             }
+        }
+        .map(|mut value| {
+            value.fraction = fraction_unit.to_nanoseconds(value.fraction);
+            value
+        }))
+    }
+
+    /// Reads GUID value from column as raw bytes.
+    pub fn into_guid(self) -> Result<Option<Guid>, DatumAccessError> {
+        Ok(match self.column_type.odbc_type {
+            SqlDataType::SQL_EXT_GUID => self.into_guid_bytes()?,
+            queried => {
+                return Err(DatumAccessError::SqlDataTypeMismatch(SqlDataTypeMismatch {
+                    requested: "GUID",
+                    queried,
+                }))
+            }
         })
     }
 
@@ -451,6 +1025,12 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
     /// Reads `serde_json::Value` value from column.
     pub fn into_json(self) -> Result<Option<serde_json::Value>, DatumAccessError> {
         Ok(match self.column_type.odbc_type {
+            // configured with `Settings::json_columns` or `Settings::json_type_names`
+            _ if self.is_json_column() => {
+                self.into_string()?
+                    .map(|data| serde_json::from_str(&data).map_err(Into::into))
+                    .transpose()?
+            }
             queried @ SqlDataType::SQL_UNKNOWN_TYPE => {
                 self.into::<String>()?
                     .map(|data| {
@@ -480,6 +1060,39 @@ impl<'r, 's, 'c, S, C: Configuration> Column<'r, 's, 'c, S, C> {
         })
     }
 
+    #[cfg(feature = "serde_json")]
+    /// `true` if column was configured to hold JSON text with `Settings::json_columns` or `Settings::json_type_names`.
+    pub(crate) fn is_json_column(&self) -> bool {
+        self.settings.is_json_column(&self.column_type.name, self.type_name())
+    }
+
+    #[cfg(feature = "serde_json")]
+    /// Reads `Value::Json` from column configured to hold JSON text falling back to `Value::String` with a warning if the
+    /// text is not valid JSON.
+    pub(crate) fn into_json_value(self) -> Result<Option<Value>, DatumAccessError> {
+        let column_type = self.column_type;
+        Ok(self.into_string()?.map(|data| match serde_json::from_str(&data) {
+            Ok(json) => Value::Json(json),
+            Err(err) => {
+                log::warn!("Value of JSON column '{}' is not valid JSON ({}); using string value", column_type.name, err);
+                Value::String(data)
+            }
+        }))
+    }
+
+    /// Escape hatch for fetching column data with C types not used by this crate.
+    ///
+    /// Calls `f` with the raw ODBC cursor and ODBC column number of this column (first column is 1) so the value can be fetched
+    /// with `odbc::Cursor::get_data`. Use `column_type` for the column description.
+    /// Note that drivers may only allow getting column data once and in column order.
+    ///
+    /// Result sets with columns of SQL data types not supported by this crate fail to be created with
+    /// `ResultSetError::UnsupportedSqlDataType`; to read such columns here map their type with `Settings::read_as` first
+    /// (e.g. to `DatumType::String`).
+    pub fn with_raw_cursor<O>(self, f: impl FnOnce(&mut odbc::Cursor<'s, 'c, 'c, S>, u16) -> O) -> O {
+        f(self.cursor, self.index + 1)
+    }
+
     /// Gets column number in the row (first column is 0)
     pub fn index(&self) -> u16 {
         self.index
@@ -500,6 +1113,12 @@ pub struct Row<'r, 's, 'c, S, C: Configuration> {
     index: u16,
     /// Number of columns
     columns: u16,
+    /// Result set column index of each `schema` column if only some columns are fetched
+    column_numbers: Option<&'r [u16]>,
+    /// Properties of `schema` columns not part of `ColumnType`; empty if not provided
+    details: &'r [ColumnDetails],
+    /// Statement handle and warnings of `ResultSet` to add diagnostics of column reads to
+    warnings: Option<(odbc::ffi::SQLHSTMT, &'r mut Vec<DriverWarning>)>,
 }
 
 impl<'r, 's, 'c, S, C: Configuration> fmt::Debug for Row<'r, 's, 'c, S, C> {
@@ -528,19 +1147,40 @@ impl<'r, 's, 'c, S, C: Configuration> Row<'r, 's, 'c, S, C> {
             cursor,
             index: 0,
             columns: schema.len() as u16,
+            column_numbers: None,
+            details: &[],
+            warnings: None,
         }
     }
 
+    /// Map `schema` columns to given result set column indices (see `ResultSet::select_columns()`).
+    pub(crate) fn with_column_numbers(self, column_numbers: Option<&'r [u16]>) -> Row<'r, 's, 'c, S, C> {
+        Row { column_numbers, ..self }
+    }
+
+    /// Provide `ColumnDetails` of `schema` columns (e.g. driver specific type names used by `Settings::vendor_types`).
+    pub(crate) fn with_details(self, details: &'r [ColumnDetails]) -> Row<'r, 's, 'c, S, C> {
+        Row { details, ..self }
+    }
+
+    /// Collect warnings reported by the driver when reading columns of this row (see `ResultSet::warnings()`).
+    pub(crate) fn with_warnings(self, handle: odbc::ffi::SQLHSTMT, warnings: &'r mut Vec<DriverWarning>) -> Row<'r, 's, 'c, S, C> {
+        Row { warnings: Some((handle, warnings)), ..self }
+    }
+
     pub fn shift_column<'i>(&'i mut self) -> Option<Column<'i, 's, 'c, S, C>> {
         self.schema
             .get(self.index as usize)
             .map(move |column_type| {
+                let index = self.column_numbers.map(|numbers| numbers[self.index as usize]).unwrap_or(self.index);
                 let column = Column {
                     column_type,
                     configuration: self.configuration,
                     settings: &self.settings,
                     cursor: &mut self.cursor,
-                    index: self.index,
+                    index,
+                    details: self.details.get(self.index as usize),
+                    warnings: self.warnings.as_mut().map(|(handle, warnings)| (*handle, &mut **warnings)),
                 };
 
                 self.index += 1;
@@ -574,6 +1214,13 @@ pub trait TryFromRow<C: Configuration>: Sized {
     type Error: Error + 'static;
     /// Given `ColumnType` convert from `Row` to other type of value representing table row.
     fn try_from_row<'r, 's, 'c, S>(row: Row<'r, 's, 'c, S, C>) -> Result<Self, Self::Error>;
+
+    /// Number of columns consumed by the conversion if it is fixed (e.g. for tuples); `None` if unknown (default).
+    ///
+    /// With `Settings::strict_column_count` creating `ResultSet` of this type fails if the query returns more columns.
+    fn expected_columns() -> Option<u16> {
+        None
+    }
 }
 
 /// Error type that represents different problems when converting column values to specific types.
@@ -584,6 +1231,10 @@ pub enum ColumnConvertError {
     ValueOutOfRange {
         expected: &'static str,
     },
+    /// Exact numeric value has non-zero fractional part that can not be represented by the integer type.
+    FractionalValue {
+        expected: &'static str,
+    },
 }
 
 impl From<DatumAccessError> for ColumnConvertError {
@@ -604,6 +1255,9 @@ impl fmt::Display for ColumnConvertError {
             ColumnConvertError::ValueOutOfRange { expected } => {
                 write!(f, "value is out of range for type {}", expected)
             }
+            ColumnConvertError::FractionalValue { expected } => {
+                write!(f, "value has non-zero fractional part that can not be represented by type {}", expected)
+            }
         }
     }
 }
@@ -613,7 +1267,8 @@ impl Error for ColumnConvertError {
         match self {
             ColumnConvertError::DatumAccessError(err) => Some(err),
             ColumnConvertError::UnexpectedNullValue(_) |
-            ColumnConvertError::ValueOutOfRange { .. } => None,
+            ColumnConvertError::ValueOutOfRange { .. } |
+            ColumnConvertError::FractionalValue { .. } => None,
         }
     }
 }
@@ -671,7 +1326,56 @@ try_from_row_unsigned![i16, u16];
 try_from_row![i32, into_i32];
 try_from_row_unsigned![i32, u32];
 try_from_row![i64, into_i64];
-try_from_row_unsigned![i64, u64];
+try_from_row![Guid, into_guid];
+
+/// Parse exact string representation of DECIMAL/NUMERIC value as `u64` accepting zero fractional part (e.g. "5.00" of
+/// NUMERIC(20, 2)).
+pub(crate) fn parse_u64_decimal(value: &str) -> Result<u64, ColumnConvertError> {
+    let value = value.trim();
+    let (integer, fraction) = match value.find('.') {
+        Some(point) => (&value[..point], &value[point + 1..]),
+        None => (value, ""),
+    };
+
+    if fraction.bytes().any(|b| b != b'0') {
+        return Err(if fraction.bytes().all(|b| b.is_ascii_digit()) {
+            ColumnConvertError::FractionalValue { expected: "u64" }
+        } else {
+            ColumnConvertError::ValueOutOfRange { expected: "u64" }
+        });
+    }
+
+    // some drivers omit leading zero (e.g. ".00")
+    if integer.is_empty() {
+        return Ok(0);
+    }
+    integer.parse().map_err(|_| ColumnConvertError::ValueOutOfRange { expected: "u64" })
+}
+
+// values above `i64::MAX` don't fit BIGINT and are stored as DECIMAL/NUMERIC(20, 0) so read them from exact string representation
+impl<C: Configuration> TryFromColumn<C> for Option<u64> {
+    type Error = ColumnConvertError;
+    fn try_from_column<'i, 's, 'c, S>(column: Column<'i, 's, 'c, S, C>) -> Result<Self, Self::Error> {
+        match column.column_type.odbc_type {
+            SqlDataType::SQL_DECIMAL | SqlDataType::SQL_NUMERIC => column
+                .into_string()?
+                .map(|value| parse_u64_decimal(&value))
+                .transpose(),
+            _ => {
+                let value: Option<i64> = TryFromColumn::try_from_column(column)?;
+                value.map(|value|
+                    value
+                    .try_into()
+                    .map_err(|_| ColumnConvertError::ValueOutOfRange {
+                        expected: "u64",
+                    })
+                ).transpose()
+            }
+        }
+    }
+}
+
+try_from_row_not_null!(u64);
 try_from_row![f32, into_f32];
 try_from_row![f64, into_f64];
 try_from_row![String, into_string];
@@ -823,6 +1527,27 @@ where
     }
 }
 
+/// Convert row with any number of columns to homogeneous vector of any type implementing `TryFromColumn` (e.g. `Vec<Option<f64>>`).
+///
+/// This also provides conversion to `ValueRow`.
+impl<T> TryFromRow<DefaultConfiguration> for Vec<T>
+where
+    T: TryFromColumn<DefaultConfiguration>,
+{
+    type Error = RowConvertError;
+    fn try_from_row<'r, 's, 'c, S>(mut row: Row<'r, 's, 'c, S, DefaultConfiguration>) -> Result<Self, Self::Error> {
+        let mut values = Vec::with_capacity(row.columns() as usize);
+
+        while let Some(column) = row.shift_column() {
+            let value = TryFromColumn::try_from_column(column)
+                .map_err(|e| RowConvertError::ColumnConvertError(Box::new(e)))?;
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+}
+
 /// Errors that my arise when converting rows to tuples.
 #[derive(Debug)]
 pub enum RowConvertTupleError {
@@ -874,6 +1599,10 @@ macro_rules! try_from_tuple {
                     }
                     Ok(($({ let x: $T = $T::try_from_column(row.shift_column().unwrap()).map_err(|err| RowConvertTupleError::ValueConvertError(Box::new(err)))?; x},)+))
                 }
+
+                fn expected_columns() -> Option<u16> {
+                    Some(count!($($T)+))
+                }
             }
         )+
     }
@@ -1229,3 +1958,255 @@ try_from_tuple! {
         (24) -> TY
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column_descriptor(data_type: SqlDataType, column_size: Option<odbc::ffi::SQLULEN>) -> ColumnDescriptor {
+        ColumnDescriptor {
+            name: "foo".to_owned(),
+            data_type,
+            column_size,
+            decimal_digits: None,
+            nullable: Some(true),
+        }
+    }
+
+    #[test]
+    fn test_column_details_unbounded_size() {
+        let column_size = |data_type, column_size| ColumnDetails::new(&column_descriptor(data_type, column_size), None).column_size;
+        assert_eq!(column_size(SqlDataType::SQL_VARCHAR, Some(10)), Some(10));
+        assert_eq!(column_size(SqlDataType::SQL_VARCHAR, Some(0)), None);
+        assert_eq!(column_size(SqlDataType::SQL_EXT_LONGVARCHAR, Some(2147483647)), None);
+        assert_eq!(column_size(SqlDataType::SQL_EXT_WLONGVARCHAR, Some(1073741823)), None);
+        assert_eq!(column_size(SqlDataType::SQL_VARCHAR, Some(8000)), Some(8000));
+        assert_eq!(column_size(SqlDataType::SQL_VARCHAR, None), None);
+    }
+
+    #[test]
+    fn test_column_type_numbers_as_strings() {
+        let settings = Settings {
+            numbers_as_strings: true,
+            read_as: vec![(SqlDataType::SQL_DOUBLE, DatumType::Double)],
+            ..Default::default()
+        };
+
+        let column_type = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_EXT_BIGINT, None), None, &settings).unwrap().0;
+        assert_eq!(column_type.datum_type, DatumType::String);
+        let column_type = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_DECIMAL, None), None, &settings).unwrap().0;
+        assert_eq!(column_type.datum_type, DatumType::String);
+        let column_type = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_DOUBLE, None), None, &settings).unwrap().0;
+        assert_eq!(column_type.datum_type, DatumType::Double);
+        let column_type = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_EXT_BIT, Some(1)), None, &settings).unwrap().0;
+        assert_eq!(column_type.datum_type, DatumType::Bit);
+    }
+
+    #[test]
+    fn test_column_type_bit() {
+        let column_type = ColumnType::try_from(column_descriptor(SqlDataType::SQL_EXT_BIT, Some(1))).unwrap();
+        assert_eq!(column_type.datum_type, DatumType::Bit);
+
+        let column_type = ColumnType::try_from(column_descriptor(SqlDataType::SQL_EXT_BIT, None)).unwrap();
+        assert_eq!(column_type.datum_type, DatumType::Bit);
+    }
+
+    #[test]
+    fn test_column_type_bit_string() {
+        let column_type = ColumnType::try_from(column_descriptor(SqlDataType::SQL_EXT_BIT, Some(8))).unwrap();
+        assert_eq!(column_type.datum_type, DatumType::String);
+        assert_eq!(column_type.odbc_type, SqlDataType::SQL_EXT_BIT);
+    }
+
+    #[test]
+    fn test_column_type_read_as() {
+        let settings = Settings {
+            read_as: vec![(SqlDataType::SQL_CHAR, DatumType::Bit)],
+            ..Default::default()
+        };
+
+        let column_type = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_CHAR, Some(1)), None, &settings).unwrap().0;
+        assert_eq!(column_type.datum_type, DatumType::Bit);
+        assert_eq!(column_type.odbc_type, SqlDataType::SQL_CHAR);
+
+        let column_type = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_VARCHAR, Some(1)), None, &settings).unwrap().0;
+        assert_eq!(column_type.datum_type, DatumType::String);
+    }
+
+    #[test]
+    fn test_column_type_driver_default_types() {
+        assert!(ColumnType::try_from(column_descriptor(SqlDataType::SQL_SS_XML, None)).is_err());
+
+        let settings = Settings {
+            driver_default_types: vec![SqlDataType::SQL_SS_XML],
+            ..Default::default()
+        };
+
+        let column_type = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_SS_XML, None), None, &settings).unwrap().0;
+        assert_eq!(column_type.datum_type, DatumType::String);
+        assert_eq!(column_type.odbc_type, SqlDataType::SQL_SS_XML);
+    }
+
+    #[test]
+    fn test_column_type_character_large_object() {
+        let settings = Settings::default();
+
+        for type_name in &["CLOB", "nclob", "DBCLOB"] {
+            let (column_type, details) = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_UNKNOWN_TYPE, None), Some(type_name.to_string()), &settings).unwrap();
+            assert_eq!(column_type.datum_type, DatumType::String);
+            assert_eq!(column_type.odbc_type, SqlDataType::SQL_UNKNOWN_TYPE);
+            assert_eq!(details.column_size, None);
+        }
+
+        assert_eq!(character_large_object(Some("CLOB")), Some(false));
+        assert_eq!(character_large_object(Some("NCLOB")), Some(true));
+        assert_eq!(character_large_object(Some("BLOB")), None);
+        assert_eq!(character_large_object(None), None);
+    }
+
+    #[test]
+    fn test_expected_columns() {
+        assert_eq!(<(i32,) as TryFromRow<DefaultConfiguration>>::expected_columns(), Some(1));
+        assert_eq!(<(i32, String, bool) as TryFromRow<DefaultConfiguration>>::expected_columns(), Some(3));
+        assert_eq!(<Vec<Option<Value>> as TryFromRow<DefaultConfiguration>>::expected_columns(), None);
+    }
+
+    #[test]
+    fn test_parse_u64_decimal() {
+        assert_eq!(parse_u64_decimal("18446744073709551615").unwrap(), std::u64::MAX);
+        assert_eq!(parse_u64_decimal("5.00").unwrap(), 5);
+        assert_eq!(parse_u64_decimal(".00").unwrap(), 0);
+        assert!(matches!(parse_u64_decimal("5.01"), Err(ColumnConvertError::FractionalValue { expected: "u64" })));
+        assert!(matches!(parse_u64_decimal("-5.00"), Err(ColumnConvertError::ValueOutOfRange { expected: "u64" })));
+        assert!(matches!(parse_u64_decimal("18446744073709551616"), Err(ColumnConvertError::ValueOutOfRange { expected: "u64" })));
+    }
+
+    #[test]
+    fn test_guid() {
+        let column_type = ColumnType::try_from(column_descriptor(SqlDataType::SQL_EXT_GUID, Some(36))).unwrap();
+        assert_eq!(column_type.datum_type, DatumType::String);
+
+        let guid = Guid::convert(&[0xff, 0x19, 0x96, 0x6f, 0x86, 0x8b, 0x11, 0xd0, 0xb4, 0x2d, 0x00, 0xc0, 0x4f, 0xc9, 0x64, 0xff]);
+        assert_eq!(guid.to_canonical_string(), "6f9619ff-8b86-d011-b42d-00c04fc964ff");
+        assert_eq!(guid.to_string(), "6f9619ff-8b86-d011-b42d-00c04fc964ff");
+        assert_eq!(guid.as_bytes()[0], 0xff);
+        assert_eq!(Guid::from_slice(guid.as_bytes()), Some(guid));
+        assert_eq!(Guid::from_slice(&guid.as_bytes()[..15]), None);
+    }
+
+    #[test]
+    fn test_fraction_unit_to_nanoseconds() {
+        assert_eq!(FractionUnit::default().to_nanoseconds(123_456_789), 123_456_789);
+        assert_eq!(FractionUnit::HundredNanoseconds.to_nanoseconds(1_234_567), 123_456_700);
+        assert_eq!(FractionUnit::Microseconds.to_nanoseconds(123_456), 123_456_000);
+        assert_eq!(FractionUnit::Milliseconds.to_nanoseconds(123), 123_000_000);
+        assert_eq!(FractionUnit::Milliseconds.to_nanoseconds(123_456), 999_999_999);
+    }
+
+    #[test]
+    fn test_column_type_vendor_type() {
+        let settings = Settings {
+            vendor_types: vec![VendorType {
+                sql_type: SqlDataType::SQL_VARCHAR,
+                type_name: "hstore".to_owned(),
+                decoder: |data| Ok(Value::String(data)),
+            }],
+            ..Default::default()
+        };
+
+        let (column_type, details) = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_VARCHAR, None), Some("HSTORE".to_owned()), &settings).unwrap();
+        assert_eq!(column_type.datum_type, DatumType::String);
+        assert_eq!(details.type_name.as_deref(), Some("HSTORE"));
+        assert!(settings.vendor_types[0].matches(column_type.odbc_type, details.type_name.as_deref()));
+
+        assert!(!settings.vendor_types[0].matches(SqlDataType::SQL_VARCHAR, Some("text")));
+        assert!(!settings.vendor_types[0].matches(SqlDataType::SQL_VARCHAR, None));
+        assert!(!settings.vendor_types[0].matches(SqlDataType::SQL_CHAR, Some("hstore")));
+    }
+
+    fn column_type(name: &str) -> ColumnType {
+        let mut column_type = ColumnType::try_from(column_descriptor(SqlDataType::SQL_INTEGER, None)).unwrap();
+        column_type.name = name.to_owned();
+        column_type
+    }
+
+    fn names(schema: &[ColumnType]) -> Vec<&str> {
+        schema.iter().map(|column_type| column_type.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_to_hex() {
+        assert_eq!(to_hex(&[0, 0, 0, 0, 0, 0, 0x07, 0xd1]), "00000000000007d1");
+        assert!(to_hex(&[0, 0x0a]) < to_hex(&[0, 0x10]));
+        assert_eq!(to_hex(&[]), "");
+    }
+
+    #[test]
+    fn test_timestamp_offset_to_rfc3339() {
+        assert_eq!(timestamp_offset_to_rfc3339("2018-08-24 10:22:33.0000000 +02:00"), "2018-08-24T10:22:33+02:00");
+        assert_eq!(timestamp_offset_to_rfc3339("2018-08-24 10:22:33.1230000 -05:30"), "2018-08-24T10:22:33.123-05:30");
+        assert_eq!(timestamp_offset_to_rfc3339("2018-08-24 10:22:30 +00:00"), "2018-08-24T10:22:30+00:00");
+        assert_eq!(timestamp_offset_to_rfc3339("foo"), "foo");
+    }
+
+    #[test]
+    fn test_column_info() {
+        let mut column_descriptor = column_descriptor(SqlDataType::SQL_DECIMAL, Some(19));
+        column_descriptor.decimal_digits = Some(4);
+        let (column_type, details) = ColumnType::with_settings(column_descriptor, None, &Settings::default()).unwrap();
+
+        assert_eq!(ColumnInfo::new(&column_type, &details), ColumnInfo {
+            name: "foo".to_owned(),
+            sql_type: "SQL_DECIMAL".to_owned(),
+            nullable: true,
+            column_size: Some(19),
+            decimal_digits: Some(4),
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_column_info_serialize() {
+        let (column_type, details) = ColumnType::with_settings(column_descriptor(SqlDataType::SQL_VARCHAR, Some(10)), None, &Settings::default()).unwrap();
+        let column_info = ColumnInfo::new(&column_type, &details);
+
+        assert_eq!(
+            &serde_json::to_string(&column_info).unwrap(),
+            r#"{"name":"foo","sql_type":"SQL_VARCHAR","nullable":true,"column_size":10,"decimal_digits":null}"#
+        );
+    }
+
+    #[test]
+    fn test_name_unnamed_columns() {
+        let mut schema = vec![column_type(""), column_type("foo"), column_type(""), column_type("col_4"), column_type("")];
+        name_unnamed_columns(&mut schema);
+        assert_eq!(names(&schema), ["col_1", "foo", "col_3", "col_4", "col_5"]);
+
+        let mut schema = vec![column_type("col_2"), column_type("")];
+        name_unnamed_columns(&mut schema);
+        assert_eq!(names(&schema), ["col_2", "col_2_2"]);
+    }
+
+    #[test]
+    fn test_duplicate_column_names_keep() {
+        let mut schema = vec![column_type("id"), column_type("id")];
+        resolve_duplicate_column_names(&mut schema, DuplicateColumnNames::Keep).unwrap();
+        assert_eq!(names(&schema), ["id", "id"]);
+    }
+
+    #[test]
+    fn test_duplicate_column_names_fail() {
+        let mut schema = vec![column_type("id"), column_type("foo"), column_type("id")];
+        assert_eq!(resolve_duplicate_column_names(&mut schema, DuplicateColumnNames::Fail).unwrap_err(), "id");
+
+        let mut schema = vec![column_type(""), column_type("")];
+        assert!(resolve_duplicate_column_names(&mut schema, DuplicateColumnNames::Fail).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_column_names_rename() {
+        let mut schema = vec![column_type("id"), column_type("id_2"), column_type("id"), column_type("id"), column_type("")];
+        resolve_duplicate_column_names(&mut schema, DuplicateColumnNames::Rename).unwrap();
+        assert_eq!(names(&schema), ["id", "id_2", "id_3", "id_4", ""]);
+    }
+}
@@ -0,0 +1,224 @@
+//! Running multi-statement SQL scripts (e.g. migrations) statement by statement.
+
+use log::{debug, warn};
+use std::error::Error;
+use std::fmt;
+
+use crate::query::{Handle, QueryError};
+use crate::row::{Configuration, DefaultConfiguration};
+use crate::value_row::ValueRow;
+use crate::{split_queries, SplitQueriesError};
+
+/// Failure of single statement of a script.
+#[derive(Debug)]
+pub struct StatementError {
+    /// Index of the statement in the script (starting from 0).
+    pub index: usize,
+    /// Text of the failed statement.
+    pub statement: String,
+    pub error: QueryError,
+}
+
+impl fmt::Display for StatementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "script statement {} failed", self.index)
+    }
+}
+
+impl Error for StatementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Errors running a script.
+#[derive(Debug)]
+pub enum ScriptError {
+    /// Script could not be split into statements; no statements after the failure point were run.
+    SplitQueriesError(SplitQueriesError),
+    /// Statement failed and script was stopped (see `Script::continue_on_error()`); contains summary of statements run
+    /// so far not including the failed one.
+    StatementError(StatementError, ScriptSummary),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScriptError::SplitQueriesError(_) => write!(f, "failed to split script into statements"),
+            ScriptError::StatementError(err, _) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ScriptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScriptError::SplitQueriesError(err) => Some(err),
+            ScriptError::StatementError(err, _) => err.source(),
+        }
+    }
+}
+
+impl From<SplitQueriesError> for ScriptError {
+    fn from(err: SplitQueriesError) -> ScriptError {
+        ScriptError::SplitQueriesError(err)
+    }
+}
+
+/// Counts of script statements run with `Script::run()`.
+#[derive(Debug, Default)]
+pub struct ScriptSummary {
+    /// Number of statements that completed successfully.
+    pub succeeded: usize,
+    /// Number of rows returned by all statements (rows are discarded).
+    pub rows: u64,
+    /// Sum of the number of rows affected by statements as reported by the driver.
+    pub affected_rows: i64,
+    /// Statements that failed when running with `Script::continue_on_error(true)`.
+    pub failed: Vec<StatementError>,
+}
+
+impl ScriptSummary {
+    /// Total number of statements run.
+    pub fn executed(&self) -> usize {
+        self.succeeded + self.failed.len()
+    }
+}
+
+/// Runner of multi-statement SQL script created with `Handle::script()`.
+///
+/// Script is split with `split_queries()` and each statement is executed as one-off query with any returned rows
+/// discarded.
+pub struct Script<'h, 'c, 's, C: Configuration> {
+    handle: &'h mut Handle<'c, C>,
+    script: &'s str,
+    on_statement: Option<Box<dyn FnMut(usize, &str) + 'h>>,
+    continue_on_error: bool,
+}
+
+impl<'h, 'c, 's, C: Configuration> fmt::Debug for Script<'h, 'c, 's, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Script")
+            .field("script", &self.script)
+            .field("continue_on_error", &self.continue_on_error)
+            .finish()
+    }
+}
+
+impl<'c, C: Configuration> Handle<'c, C> {
+    /// Create runner of multi-statement SQL script.
+    pub fn script<'h, 's>(&'h mut self, script: &'s str) -> Script<'h, 'c, 's, C> {
+        Script {
+            handle: self,
+            script,
+            on_statement: None,
+            continue_on_error: false,
+        }
+    }
+}
+
+impl<'h, 'c, 's, C: Configuration> Script<'h, 'c, 's, C> {
+    /// Call given function with index and text of each statement before it is executed.
+    pub fn on_statement(mut self, on_statement: impl FnMut(usize, &str) + 'h) -> Self {
+        self.on_statement = Some(Box::new(on_statement));
+        self
+    }
+
+    /// When `true` failed statements are recorded in `ScriptSummary::failed` and following statements are still run;
+    /// otherwise (default) script is stopped with `ScriptError::StatementError` on first failure.
+    pub fn continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Run all statements of the script in order.
+    pub fn run(mut self) -> Result<ScriptSummary, ScriptError> {
+        let mut summary = ScriptSummary::default();
+
+        for (index, statement) in split_queries(self.script).enumerate() {
+            let statement = statement?;
+            if let Some(on_statement) = self.on_statement.as_mut() {
+                on_statement(index, statement);
+            }
+
+            match run_statement(self.handle, statement) {
+                Ok((rows, affected_rows)) => {
+                    debug!("Script statement {} returned {} rows and affected {:?} rows", index, rows, affected_rows);
+                    summary.succeeded += 1;
+                    summary.rows += rows;
+                    summary.affected_rows += affected_rows.unwrap_or(0);
+                }
+                Err(error) => {
+                    let error = StatementError {
+                        index,
+                        statement: statement.to_owned(),
+                        error,
+                    };
+                    if !self.continue_on_error {
+                        return Err(ScriptError::StatementError(error, summary));
+                    }
+                    warn!("Script statement {} failed; continuing: {}", index, error.error);
+                    summary.failed.push(error);
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+fn run_statement<C: Configuration>(handle: &mut Handle<'_, C>, statement: &str) -> Result<(u64, Option<i64>), QueryError> {
+    let mut handle = handle.with_configuration(DefaultConfiguration);
+    let mut result_set = handle.query::<ValueRow>(statement)?;
+    let affected_rows = result_set.affected_rows()?;
+
+    let mut rows = 0;
+    for row in &mut result_set {
+        row?;
+        rows += 1;
+    }
+    Ok((rows, affected_rows))
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use assert_matches::assert_matches;
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_sql_server_script() {
+        let mut connection = crate::tests::connect_sql_server();
+        let mut handle = connection.handle();
+
+        let mut statements = Vec::new();
+        let summary = handle
+            .script("CREATE TABLE #script (id INT);\nINSERT INTO #script VALUES (1), (2);\nSELECT * FROM #script;\nDROP TABLE #script;")
+            .on_statement(|index, statement| statements.push((index, statement.to_owned())))
+            .run()
+            .expect("run script");
+
+        assert_eq!(summary.succeeded, 4);
+        assert_eq!(summary.executed(), 4);
+        assert_eq!(summary.rows, 2);
+        assert_eq!(summary.affected_rows, 2);
+        assert_eq!(statements[1], (1, "INSERT INTO #script VALUES (1), (2);".to_owned()));
+
+        let summary = handle
+            .script("SELECT 1;\nSELECT * FROM script_does_not_exist;\nSELECT 2;")
+            .continue_on_error(true)
+            .run()
+            .expect("run script");
+
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].index, 1);
+
+        assert_matches!(
+            handle.script("SELECT 1;\nSELECT * FROM script_does_not_exist;\nSELECT 2;").run(),
+            Err(ScriptError::StatementError(StatementError { index: 1, .. }, ScriptSummary { succeeded: 1, .. }))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Optional `tracing` spans around connect, prepare, execute and fetch operations (with "tracing" feature).
+//! Without the feature all operations are no-ops and only `log` macros are used.
+
+#[cfg(feature = "tracing")]
+mod inner {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::time::Instant;
+    use tracing::field::Empty;
+    use tracing::span::EnteredSpan;
+    use tracing::{debug_span, Span};
+
+    use crate::connection_string::ConnectionInfo;
+
+    // hash of query text allows correlating spans of the same query without recording (possibly sensitive) text
+    fn query_hash(query: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        query.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn elapsed_ms(start: Instant) -> u64 {
+        start.elapsed().as_millis() as u64
+    }
+
+    /// Entered span that records its duration when dropped.
+    pub(crate) struct OperationSpan {
+        span: EnteredSpan,
+        start: Instant,
+    }
+
+    impl OperationSpan {
+        fn enter(span: Span) -> OperationSpan {
+            OperationSpan {
+                span: span.entered(),
+                start: Instant::now(),
+            }
+        }
+    }
+
+    impl Drop for OperationSpan {
+        fn drop(&mut self) {
+            self.span.record("duration_ms", &elapsed_ms(self.start));
+        }
+    }
+
+    pub(crate) fn connect(info: &ConnectionInfo) -> OperationSpan {
+        OperationSpan::enter(debug_span!(
+            "odbc_connect",
+            server = info.server.as_deref().or_else(|| info.dsn.as_deref()).unwrap_or(""),
+            database = info.database.as_deref().unwrap_or(""),
+            duration_ms = Empty,
+        ))
+    }
+
+    pub(crate) fn prepare(query: &str) -> OperationSpan {
+        OperationSpan::enter(debug_span!(
+            "odbc_prepare",
+            query_hash = query_hash(query),
+            duration_ms = Empty,
+        ))
+    }
+
+    /// `None` query for prepared statements.
+    pub(crate) fn execute(query: Option<&str>) -> OperationSpan {
+        let span = debug_span!("odbc_execute", query_hash = Empty, duration_ms = Empty);
+        if let Some(query) = query {
+            span.record("query_hash", &query_hash(query));
+        }
+        OperationSpan::enter(span)
+    }
+
+    /// Span covering lifetime of a result set; it is not entered as fetching is interleaved with user code.
+    pub(crate) struct FetchSpan {
+        span: Span,
+        start: Instant,
+    }
+
+    impl FetchSpan {
+        pub(crate) fn finish(&self, rows: u64) {
+            self.span.record("rows", &rows);
+            self.span.record("duration_ms", &elapsed_ms(self.start));
+        }
+    }
+
+    /// Created as child of current (execute) span.
+    pub(crate) fn fetch() -> FetchSpan {
+        FetchSpan {
+            span: debug_span!("odbc_fetch", rows = Empty, duration_ms = Empty),
+            start: Instant::now(),
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod inner {
+    use crate::connection_string::ConnectionInfo;
+
+    pub(crate) struct OperationSpan;
+
+    pub(crate) fn connect(_info: &ConnectionInfo) -> OperationSpan {
+        OperationSpan
+    }
+
+    pub(crate) fn prepare(_query: &str) -> OperationSpan {
+        OperationSpan
+    }
+
+    pub(crate) fn execute(_query: Option<&str>) -> OperationSpan {
+        OperationSpan
+    }
+
+    pub(crate) struct FetchSpan;
+
+    impl FetchSpan {
+        pub(crate) fn finish(&self, _rows: u64) {}
+    }
+
+    pub(crate) fn fetch() -> FetchSpan {
+        FetchSpan
+    }
+}
+
+pub(crate) use inner::{connect, execute, fetch, prepare, FetchSpan};
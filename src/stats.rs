@@ -189,3 +189,7 @@ pub(crate) fn query_execution<O, E, F>(f: F) -> Result<(O, QueryFetchingGuard),
         }
     }
 }
+
+pub(crate) fn query_fetching() -> QueryFetchingGuard {
+    QueryFetchingGuard::new()
+}
@@ -0,0 +1,149 @@
+//! Consuming query rows as async `Stream` (with "tokio" feature).
+//!
+//! ODBC calls are blocking so the query runs on dedicated thread that sends rows over bounded channel.
+
+use futures_core::Stream;
+use log::debug;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread;
+use tokio::sync::mpsc::{channel, Receiver};
+
+use crate::query::QueryError;
+use crate::value_row::ValueRow;
+use crate::Odbc;
+
+// rows buffered before query thread waits for the consumer
+const STREAM_BUFFER: usize = 1024;
+
+/// Failure of streamed query; no more rows are provided after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamError {
+    /// Description of the error including its causes.
+    pub message: String,
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "streamed query failed: {}", self.message)
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<QueryError> for StreamError {
+    fn from(error: QueryError) -> StreamError {
+        let mut message = error.to_string();
+        let mut cause = error.source();
+        while let Some(err) = cause {
+            message.push_str(": ");
+            message.push_str(&err.to_string());
+            cause = err.source();
+        }
+        StreamError { message }
+    }
+}
+
+/// Async stream of rows of query.
+///
+/// Created with `query_stream()`.
+#[derive(Debug)]
+pub struct RowStream {
+    rows: Receiver<Result<ValueRow, StreamError>>,
+}
+
+impl Stream for RowStream {
+    type Item = Result<ValueRow, StreamError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rows.poll_recv(cx)
+    }
+}
+
+/// Connect to the database and run query on dedicated thread providing its rows as async `Stream`.
+///
+/// Rows are fetched ahead of the consumer up to the channel capacity; after that the query thread waits until rows are
+/// consumed. Failure to connect, execute the query or fetch a row is provided as last item of the stream.
+/// Dropping the `RowStream` stops the thread after it tries to provide next row.
+///
+/// Use `StreamExt::next()` of `futures` or `tokio-stream` crate to consume rows with
+/// `while let Some(row) = stream.next().await`.
+pub fn query_stream(connection_string: &str, query: &str) -> RowStream {
+    let (sender, rows) = channel(STREAM_BUFFER);
+    let connection_string = connection_string.to_owned();
+    let query = query.to_owned();
+
+    thread::spawn(move || {
+        let result = Odbc::connect(&connection_string)
+            .map_err(QueryError::from)
+            .and_then(|mut connection| -> Result<(), QueryError> {
+                for row in connection.handle().query::<ValueRow>(&query)? {
+                    let row = row.map_err(|err| StreamError::from(QueryError::from(err)));
+                    if sender.blocking_send(row).is_err() {
+                        debug!("Stream consumer gone; stopping query");
+                        break;
+                    }
+                }
+                Ok(())
+            });
+
+        if let Err(err) = result {
+            sender.blocking_send(Err(err.into())).ok();
+        }
+    });
+
+    RowStream { rows }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::Value;
+    #[allow(unused_imports)]
+    use std::sync::Arc;
+    #[allow(unused_imports)]
+    use std::task::Wake;
+
+    // minimal executor so tests do not need tokio runtime; receiving from the channel does not depend on one
+    #[cfg(feature = "test-sql-server")]
+    struct ThreadWaker(thread::Thread);
+
+    #[cfg(feature = "test-sql-server")]
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    fn next_blocking(stream: &mut RowStream) -> Option<Result<ValueRow, StreamError>> {
+        let waker = std::task::Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match Pin::new(&mut *stream).poll_next(&mut cx) {
+                Poll::Ready(item) => return item,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[cfg(feature = "test-sql-server")]
+    #[test]
+    fn test_query_stream() {
+        let mut stream = query_stream(
+            crate::tests::sql_server_connection_string().as_str(),
+            "SELECT * FROM (VALUES (1), (2), (3)) AS t (id)",
+        );
+
+        let mut ids = Vec::new();
+        while let Some(row) = next_blocking(&mut stream) {
+            ids.push(row.expect("fetch row"));
+        }
+
+        assert_eq!(ids, [vec![Some(Value::Integer(1))], vec![Some(Value::Integer(2))], vec![Some(Value::Integer(3))]]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::row::{Configuration, DatumType, Column, TryFromColumn, ColumnConvertError};
+use crate::row::{Configuration, DatumType, Column, TryFromColumn, ColumnConvertError, parse_u64_decimal};
 use odbc::{SqlDate, SqlSsTime2, SqlTime, SqlTimestamp};
 use std::convert::{Infallible, TryInto};
 use std::error::Error;
@@ -310,6 +310,12 @@ impl From<String> for Value {
     }
 }
 
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::String(value.to_owned())
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl From<NaiveDateTime> for Value {
     fn from(value: NaiveDateTime) -> Value {
@@ -397,6 +403,17 @@ impl<C: Configuration> TryFromColumn<C> for Option<Value> {
     type Error = ColumnConvertError;
 
     fn try_from_column<'i, 's, 'c, S>(column: Column<'i, 's, 'c, S, C>) -> Result<Self, Self::Error> {
+        if let Some(decoder) = column.vendor_type_decoder() {
+            return Ok(column.into_vendor_value(decoder)?);
+        }
+
+        #[cfg(feature = "serde_json")]
+        {
+            if column.is_json_column() {
+                return Ok(column.into_json_value()?);
+            }
+        }
+
         Ok(match column.column_type.datum_type {
             DatumType::Bit => column.into_bool()?.map(Value::from),
             DatumType::Tinyint => column.into_i8()?.map(Value::from),
@@ -606,12 +623,49 @@ macro_rules! try_from_value_copy {
     };
 }
 
+// integer values of any width are accepted as long as they fit the requested type
+macro_rules! try_from_value_integer {
+    ($t:ty) => {
+        impl TryFromValue for $t {
+            type Error = ValueConvertError;
+            fn try_from_value(value: Option<Value>) -> Result<Self, Self::Error> {
+                let value: i64 = match value.ok_or_else(|| ValueConvertError::UnexpectedNullValue(stringify!($t)))? {
+                    Value::Tinyint(value) => value.into(),
+                    Value::Smallint(value) => value.into(),
+                    Value::Integer(value) => value.into(),
+                    Value::Bigint(value) => value,
+                    value => {
+                        return Err(ValueConvertError::UnexpectedType {
+                            expected: stringify!($t),
+                            got: value.datum_type().description(),
+                        })
+                    }
+                };
+                value
+                    .try_into()
+                    .map_err(|_| ValueConvertError::ValueOutOfRange {
+                        expected: stringify!($t),
+                    })
+            }
+        }
+
+        impl TryFromValue for Option<$t> {
+            type Error = ValueConvertError;
+            fn try_from_value(value: Option<Value>) -> Result<Self, Self::Error> {
+                value
+                    .map(|value| TryFromValue::try_from_value(Some(value)))
+                    .transpose()
+            }
+        }
+    };
+}
+
 macro_rules! try_from_value_unsigned {
-    ($it:ty, $t:ty) => {
+    ($t:ty) => {
         impl TryFromValue for $t {
             type Error = ValueConvertError;
             fn try_from_value(value: Option<Value>) -> Result<Self, Self::Error> {
-                let value: $it = TryFromValue::try_from_value(value)?;
+                let value: i64 = TryFromValue::try_from_value(value)?;
                 value
                     .try_into()
                     .map_err(|_| ValueConvertError::ValueOutOfRange {
@@ -666,14 +720,57 @@ macro_rules! try_from_value_owned {
 }
 
 try_from_value_copy![bool, to_bool];
-try_from_value_copy![i8, to_i8];
-try_from_value_unsigned![i8, u8];
-try_from_value_copy![i16, to_i16];
-try_from_value_unsigned![i16, u16];
-try_from_value_copy![i32, to_i32];
-try_from_value_unsigned![i32, u32];
-try_from_value_copy![i64, to_i64];
-try_from_value_unsigned![i64, u64];
+try_from_value_integer![i8];
+try_from_value_unsigned![u8];
+try_from_value_integer![i16];
+try_from_value_unsigned![u16];
+try_from_value_integer![i32];
+try_from_value_unsigned![u32];
+try_from_value_integer![i64];
+
+// values above `i64::MAX` can only be represented by decimal (or its string representation without "rust_decimal" feature)
+impl TryFromValue for u64 {
+    type Error = ValueConvertError;
+    fn try_from_value(value: Option<Value>) -> Result<Self, Self::Error> {
+        // same as reading the column directly; zero fractional part (e.g. "5.00" of NUMERIC(20, 2)) is accepted
+        fn parse(value: &str) -> Result<u64, ValueConvertError> {
+            if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+                return Err(ValueConvertError::UnexpectedType {
+                    expected: "u64",
+                    got: DatumType::String.description(),
+                });
+            }
+            parse_u64_decimal(value).map_err(|_| ValueConvertError::ValueOutOfRange { expected: "u64" })
+        }
+
+        match value {
+            Some(Value::String(ref value)) => parse(value),
+            #[cfg(feature = "rust_decimal")]
+            Some(Value::Decimal(ref value)) => {
+                if value.fract() != Decimal::new(0, 0) || value.is_sign_negative() {
+                    return Err(ValueConvertError::ValueOutOfRange { expected: "u64" });
+                }
+                parse(&value.trunc().to_string())
+            }
+            value => {
+                let value: i64 = TryFromValue::try_from_value(value)?;
+                value
+                    .try_into()
+                    .map_err(|_| ValueConvertError::ValueOutOfRange { expected: "u64" })
+            }
+        }
+    }
+}
+
+impl TryFromValue for Option<u64> {
+    type Error = ValueConvertError;
+    fn try_from_value(value: Option<Value>) -> Result<Self, Self::Error> {
+        value
+            .map(|value| TryFromValue::try_from_value(Some(value)))
+            .transpose()
+    }
+}
+
 try_from_value_copy![f32, to_f32];
 try_from_value_copy![f64, to_f64];
 try_from_value_owned![String, into_string];
@@ -694,6 +791,10 @@ mod ser {
     use super::*;
     use serde::{self, Serialize};
 
+    /// Integer values are serialized as numbers; all of them fit `i64` so `serde_json` does not need `arbitrary_precision`
+    /// feature. Larger integers (e.g. `u64` above `i64::MAX` stored as DECIMAL/NUMERIC(20, 0)) are available as
+    /// `Value::Decimal` (with "rust_decimal" feature) or `Value::String` and both are serialized as exact string (e.g.
+    /// `"18446744073709551615"`) to avoid loss of precision.
     impl Serialize for Value {
         #[inline]
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -818,3 +919,71 @@ mod ser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn try_from_value_u64() {
+        assert_eq!(u64::try_from_value(Some(Value::Bigint(42))).unwrap(), 42);
+        assert_eq!(
+            u64::try_from_value(Some(Value::String("18446744073709551615".to_owned()))).unwrap(),
+            std::u64::MAX
+        );
+        assert_matches!(
+            u64::try_from_value(Some(Value::String("18446744073709551616".to_owned()))),
+            Err(ValueConvertError::ValueOutOfRange { .. })
+        );
+        assert_eq!(u64::try_from_value(Some(Value::String("5.00".to_owned()))).unwrap(), 5);
+        assert_matches!(
+            u64::try_from_value(Some(Value::String("5.50".to_owned()))),
+            Err(ValueConvertError::ValueOutOfRange { .. })
+        );
+        assert_matches!(
+            u64::try_from_value(Some(Value::String("foo".to_owned()))),
+            Err(ValueConvertError::UnexpectedType { .. })
+        );
+        assert_matches!(
+            u64::try_from_value(Some(Value::Bigint(-1))),
+            Err(ValueConvertError::ValueOutOfRange { .. })
+        );
+        assert_eq!(Option::<u64>::try_from_value(None).unwrap(), None);
+    }
+
+    #[test]
+    fn try_from_value_integer() {
+        assert_eq!(i32::try_from_value(Some(Value::Bigint(42))).unwrap(), 42);
+        assert_eq!(i64::try_from_value(Some(Value::Tinyint(-42))).unwrap(), -42);
+        assert_eq!(u8::try_from_value(Some(Value::Integer(255))).unwrap(), 255);
+        assert_matches!(
+            i32::try_from_value(Some(Value::Bigint(i64::from(std::i32::MAX) + 1))),
+            Err(ValueConvertError::ValueOutOfRange { expected: "i32" })
+        );
+        assert_matches!(
+            u16::try_from_value(Some(Value::Bigint(-1))),
+            Err(ValueConvertError::ValueOutOfRange { expected: "u16" })
+        );
+        assert_matches!(
+            i32::try_from_value(Some(Value::Double(1.0))),
+            Err(ValueConvertError::UnexpectedType { expected: "i32", .. })
+        );
+        assert_eq!(Option::<i16>::try_from_value(None).unwrap(), None);
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn try_from_value_u64_decimal() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            u64::try_from_value(Some(Value::Decimal(Decimal::from_str("18446744073709551615").unwrap()))).unwrap(),
+            std::u64::MAX
+        );
+        assert_matches!(
+            u64::try_from_value(Some(Value::Decimal(Decimal::from_str("1.5").unwrap()))),
+            Err(ValueConvertError::ValueOutOfRange { .. })
+        );
+    }
+}
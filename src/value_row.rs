@@ -1,4 +1,4 @@
-use crate::row::{Row, TryFromColumn, DefaultConfiguration, TryFromRow, RowConvertError};
+use crate::row::{DefaultConfiguration, Row, RowConvertError, TryFromRow};
 use crate::value::{TryFromValue, Value};
 use std::convert::Infallible;
 use std::error::Error;
@@ -9,23 +9,6 @@ use std::fmt;
 /// This objects are constructed from row data returned by ODBC library and can be further converted to types implementing `TryFromValueRow`/`TryFromValue` traits.
 pub type ValueRow = Vec<Option<Value>>;
 
-impl TryFromRow<DefaultConfiguration> for ValueRow {
-    type Error = RowConvertError;
-
-    fn try_from_row<'r, 's, 'c, S>(mut row: Row<'r, 's, 'c, S, DefaultConfiguration>) -> Result<Self, Self::Error> {
-        let mut value_row = Vec::with_capacity(row.columns() as usize);
-
-        loop {
-            if let Some(column) = row.shift_column() {
-                let value: Option<Value> = TryFromColumn::try_from_column(column)?;
-                value_row.push(value)
-            } else {
-                return Ok(value_row);
-            }
-        }
-    }
-}
-
 // Note: TryFrom/TryInto cannot be implemented since we need to own the trait
 
 /// This traits allow for conversion of `ValueRow` type used internally by `ResultSet` iterator to any
@@ -119,6 +102,123 @@ where
     }
 }
 
+/// Borrowing alternative to `TryFromValueRow` that converts row values without taking ownership of them.
+///
+/// This is used with `ResultSet::fetch_ref` which fetches rows into a buffer reused across iterations so no new `ValueRow`
+/// needs to be allocated for each row.
+/// The lifetime `'r` is the borrow of that buffer: converted value may reference it (e.g. as `&'r str`) but then it has to be
+/// dropped before next row can be fetched into the same buffer.
+pub trait TryFromRowRef<'r>: Sized {
+    type Error: Error + 'static;
+    /// Convert from borrowed row values to other type of value representing table row.
+    fn try_from_row_ref(values: &'r [Option<Value>]) -> Result<Self, Self::Error>;
+}
+
+/// Allow to access borrowed values as they are.
+impl<'r> TryFromRowRef<'r> for &'r [Option<Value>] {
+    type Error = Infallible;
+    fn try_from_row_ref(values: &'r [Option<Value>]) -> Result<Self, Self::Error> {
+        Ok(values)
+    }
+}
+
+/// Row of values together with names of their columns providing typed access to values by column index or name.
+///
+/// This is a middle ground between working with `ValueRow` directly and implementing `TryFromRow` for custom types.
+///
+/// Example
+/// ========
+/// ```rust
+/// use odbc_iter::{Odbc, NamedRow};
+///
+/// let mut connection = Odbc::connect(&std::env::var("DB_CONNECTION_STRING").expect("no DB_CONNECTION_STRING env set")).expect("connect to database");
+///
+/// for row in connection.handle().query::<NamedRow>("SELECT 42 AS answer, 'foo' AS name").expect("failed to run query") {
+///     let row = row.expect("failed to fetch row");
+///     let answer: i64 = row.get(0).expect("no answer");
+///     let name: String = row.get_by_name("name").expect("no name");
+///     println!("{}: {}", name, answer);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedRow {
+    names: Vec<String>,
+    values: ValueRow,
+}
+
+/// Errors that may happen when accessing value of `NamedRow`.
+#[derive(Debug)]
+pub enum NamedRowError {
+    ColumnIndexOutOfRange(usize),
+    NoSuchColumn(String),
+    ValueConvertError(Box<dyn Error>),
+}
+
+impl fmt::Display for NamedRowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NamedRowError::ColumnIndexOutOfRange(index) => write!(f, "row has no column with index {}", index),
+            NamedRowError::NoSuchColumn(name) => write!(f, "row has no column named '{}'", name),
+            NamedRowError::ValueConvertError(_) => write!(f, "failed to convert column value to target type"),
+        }
+    }
+}
+
+impl Error for NamedRowError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NamedRowError::ColumnIndexOutOfRange(_) | NamedRowError::NoSuchColumn(_) => None,
+            NamedRowError::ValueConvertError(err) => Some(err.as_ref()),
+        }
+    }
+}
+
+impl NamedRow {
+    /// Create row from column names and values in the same order.
+    pub fn new(names: Vec<String>, values: ValueRow) -> NamedRow {
+        NamedRow { names, values }
+    }
+
+    /// Convert value of column with given index to type implementing `TryFromValue` (e.g. `Option<i64>` for nullable column).
+    pub fn get<T: TryFromValue>(&self, index: usize) -> Result<T, NamedRowError> {
+        let value = self.values.get(index).ok_or(NamedRowError::ColumnIndexOutOfRange(index))?;
+        T::try_from_value(value.clone()).map_err(|err| NamedRowError::ValueConvertError(Box::new(err)))
+    }
+
+    /// Convert value of first column with given name to type implementing `TryFromValue`.
+    pub fn get_by_name<T: TryFromValue>(&self, name: &str) -> Result<T, NamedRowError> {
+        let index = self.index_of(name).ok_or_else(|| NamedRowError::NoSuchColumn(name.to_owned()))?;
+        self.get(index)
+    }
+
+    /// Index of first column with given name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|column| column == name)
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn values(&self) -> &[Option<Value>] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> ValueRow {
+        self.values
+    }
+}
+
+/// Allow to retrieve rows as `NamedRow` items of `ResultSet` iterator with names taken from the result set schema.
+impl TryFromRow<DefaultConfiguration> for NamedRow {
+    type Error = RowConvertError;
+    fn try_from_row<'r, 's, 'c, S>(row: Row<'r, 's, 'c, S, DefaultConfiguration>) -> Result<Self, Self::Error> {
+        let names = row.schema.iter().map(|column_type| column_type.name.clone()).collect();
+        let values = ValueRow::try_from_row(row)?;
+        Ok(NamedRow { names, values })
+    }
+}
+
 /// Errors that my arise when converting rows to tuples.
 #[derive(Debug)]
 pub enum ValueRowConvertTupleError {
@@ -150,6 +250,38 @@ impl Error for ValueRowConvertTupleError {
     }
 }
 
+/// Construct `ValueRow` from list of values convertible to `Value` with `null` representing `NULL`.
+///
+/// This is useful for testing `TryFromValueRow` implementations without a database.
+///
+/// Example
+/// ========
+/// ```rust
+/// use odbc_iter::{value_row, TryFromValueRow, Value, ValueRow};
+///
+/// let row: ValueRow = value_row![42, "foo", null, true];
+/// assert_eq!(row, vec![Some(Value::Integer(42)), Some(Value::String("foo".to_owned())), None, Some(Value::Bit(true))]);
+///
+/// let data: (i32, String, Option<i64>, bool) = TryFromValueRow::try_from_value_row(row).expect("converted");
+/// assert_eq!(data, (42, "foo".to_owned(), None, true));
+/// ```
+#[macro_export]
+macro_rules! value_row {
+    (@items [$($acc:expr,)*]) => {{
+        let row: $crate::ValueRow = vec![$($acc),*];
+        row
+    }};
+    (@items [$($acc:expr,)*] null $(, $($rest:tt)*)?) => {
+        $crate::value_row!(@items [$($acc,)* None,] $($($rest)*)?)
+    };
+    (@items [$($acc:expr,)*] $value:expr $(, $($rest:tt)*)?) => {
+        $crate::value_row!(@items [$($acc,)* Some($crate::Value::from($value)),] $($($rest)*)?)
+    };
+    ($($item:tt)*) => {
+        $crate::value_row!(@items [] $($item)*)
+    };
+}
+
 macro_rules! count {
     () => (0usize);
     ( $x:tt $($xs:tt)* ) => (1usize + count!($($xs)*));
@@ -563,6 +695,77 @@ mod tests {
         assert_eq!(foo.val, 42);
     }
 
+    #[test]
+    fn test_value_row_macro() {
+        let test_row = value_row![42i64, "foo", null, Value::Bit(true),];
+        assert_eq!(test_row, vec![
+            Some(Value::Bigint(42)),
+            Some(Value::String("foo".to_owned())),
+            None,
+            Some(Value::Bit(true)),
+        ]);
+        assert!(value_row![].is_empty());
+
+        let foo: Foo = TryFromValueRow::try_from_value_row(value_row![42i64]).expect("failed to convert");
+        assert_eq!(foo.val, 42);
+
+        let value: (i64, String, Option<bool>) = TryFromValueRow::try_from_value_row(value_row![1i64, "x", null])
+            .expect("failed to convert");
+        assert_eq!(value, (1, "x".to_owned(), None));
+    }
+
+    struct FooRef<'r> {
+        name: &'r str,
+        val: Option<i64>,
+    }
+
+    impl<'r> TryFromRowRef<'r> for FooRef<'r> {
+        type Error = ValueRowConvertError;
+        fn try_from_row_ref(values: &'r [Option<Value>]) -> Result<Self, Self::Error> {
+            match values {
+                [Some(name), val] => Ok(FooRef {
+                    name: name.as_str().ok_or(ValueRowConvertError::UnexpectedValue)?,
+                    val: val.as_ref().and_then(|v| v.as_i64()).copied(),
+                }),
+                _ => Err(ValueRowConvertError::UnexpectedNumberOfColumns { expected: 2, got: values.len() }),
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_from_row_ref() {
+        let test_row = value_row!["foo", 42i64];
+        let foo = FooRef::try_from_row_ref(&test_row).expect("failed to convert");
+
+        assert_eq!(foo.name, "foo");
+        assert_eq!(foo.val, Some(42));
+
+        let test_row = value_row!["bar", null];
+        let foo = FooRef::try_from_row_ref(&test_row).expect("failed to convert");
+
+        assert_eq!(foo.name, "bar");
+        assert_eq!(foo.val, None);
+
+        assert_matches!(FooRef::try_from_row_ref(&value_row![1i64]), Err(ValueRowConvertError::UnexpectedNumberOfColumns { expected: 2, got: 1 }));
+    }
+
+    #[test]
+    fn test_named_row() {
+        let row = NamedRow::new(vec!["id".to_owned(), "name".to_owned(), "note".to_owned()], value_row![42i64, "foo", null]);
+
+        assert_eq!(row.get::<i64>(0).unwrap(), 42);
+        assert_eq!(row.get_by_name::<String>("name").unwrap(), "foo");
+        assert_eq!(row.get_by_name::<Option<String>>("note").unwrap(), None);
+        assert_eq!(row.index_of("note"), Some(2));
+
+        assert_matches!(row.get::<i64>(3), Err(NamedRowError::ColumnIndexOutOfRange(3)));
+        assert_matches!(row.get_by_name::<i64>("bar"), Err(NamedRowError::NoSuchColumn(ref name)) if name == "bar");
+        assert_matches!(row.get_by_name::<i64>("note"), Err(NamedRowError::ValueConvertError(_)));
+        assert_matches!(row.get::<bool>(1), Err(NamedRowError::ValueConvertError(_)));
+
+        assert_eq!(row.into_values(), value_row![42i64, "foo", null]);
+    }
+
     #[test]
     fn test_single_value() {
         let test_row: ValueRow = vec![Some(Value::Bigint(42))];